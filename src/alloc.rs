@@ -0,0 +1,160 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// A global allocator that counts the bytes allocated by each thread, so the memory used by a
+/// phase can be measured even while other days run in parallel. Install it with
+///
+/// ```ignore
+/// #[global_allocator]
+/// static ALLOCATOR: CountingAllocator = CountingAllocator;
+/// ```
+///
+/// which the binary does when built with the `alloc-stats` feature.
+pub struct CountingAllocator;
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    static LIVE: Cell<isize> = const { Cell::new(0) };
+    static BASE: Cell<isize> = const { Cell::new(0) };
+    static PEAK: Cell<isize> = const { Cell::new(0) };
+    static TOTAL: Cell<usize> = const { Cell::new(0) };
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+fn record_alloc(size: usize) {
+    // Only the first allocation writes the flag, so threads allocating in parallel just read it
+    if !INSTALLED.load(Ordering::Relaxed) {
+        INSTALLED.store(true, Ordering::Relaxed);
+    }
+    let _ = LIVE.try_with(|live| {
+        live.set(live.get() + size as isize);
+        let _ = PEAK.try_with(|peak| peak.set(peak.get().max(live.get())));
+    });
+    let _ = TOTAL.try_with(|total| total.set(total.get() + size));
+    let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+}
+
+fn record_dealloc(size: usize) {
+    let _ = LIVE.try_with(|live| live.set(live.get() - size as isize));
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record_dealloc(layout.size());
+            record_alloc(new_size);
+        }
+
+        new_ptr
+    }
+}
+
+/// The memory allocated by the current thread during a phase
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MemoryStats {
+    /// The most bytes live at once, above what was live when the phase started
+    pub peak: usize,
+    /// Every byte allocated, including any freed again before the phase ended
+    pub total: usize,
+    pub allocations: usize,
+}
+
+/// Whether the counting allocator is installed as the global allocator
+pub fn is_counting() -> bool {
+    INSTALLED.load(Ordering::Relaxed)
+}
+
+/// Start measuring a phase on the current thread
+pub fn start_phase() {
+    let live = LIVE.with(Cell::get);
+    BASE.with(|base| base.set(live));
+    PEAK.with(|peak| peak.set(live));
+    TOTAL.with(|total| total.set(0));
+    ALLOCATIONS.with(|count| count.set(0));
+}
+
+/// The memory allocated on the current thread since `start_phase`
+pub fn end_phase() -> MemoryStats {
+    MemoryStats {
+        peak: (PEAK.with(Cell::get) - BASE.with(Cell::get)).max(0) as usize,
+        total: TOTAL.with(Cell::get),
+        allocations: ALLOCATIONS.with(Cell::get),
+    }
+}
+
+/// A byte count in the largest binary unit that keeps it above 1
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.2} {}", value, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn count_phase_allocations() {
+        start_phase();
+        let buffer: Vec<u8> = Vec::with_capacity(4096);
+        drop(buffer);
+        let _small: Vec<u8> = Vec::with_capacity(1024);
+        let stats = end_phase();
+
+        assert!(is_counting());
+        assert_eq!(stats.total, 5120);
+        assert_eq!(stats.peak, 4096);
+        assert_eq!(stats.allocations, 2);
+
+        start_phase();
+        assert_eq!(end_phase(), MemoryStats::default());
+    }
+
+    #[test]
+    fn format_byte_counts() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.50 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.00 MiB");
+    }
+}
@@ -0,0 +1,238 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use toml::Value;
+
+use crate::bench::BenchResult;
+use crate::error::{Error, Result};
+use crate::toml_file::{day_tables, nest_day_tables, parse_table, TomlFile};
+
+/// Median phase timings from a previous benchmark, keyed by year and day and stored as TOML
+/// nanoseconds such as
+///
+/// ```toml
+/// [2020.day_1]
+/// setup = 9300
+/// part_1 = 3320
+/// part_2 = 6440
+/// ```
+///
+/// Days given at the top level, as in `[day_1]`, are from `DEFAULT_YEAR`.
+#[derive(Debug, Default)]
+pub struct Baseline {
+    medians: BTreeMap<(u32, u32), BTreeMap<String, Duration>>,
+}
+
+#[derive(Debug)]
+pub struct PhaseComparison {
+    pub day: u32,
+    pub phase: &'static str,
+    pub baseline: Duration,
+    pub current: Duration,
+    /// The change in median relative to the baseline, as a percentage
+    pub change: f64,
+    pub regressed: bool,
+}
+
+impl TomlFile for Baseline {
+    fn parse(s: &str) -> Result<Self> {
+        let table = parse_table(s, "days")?;
+
+        let mut medians = BTreeMap::new();
+        for day in day_tables(&table)? {
+            let mut day_medians = BTreeMap::new();
+            for (phase, nanos) in day.table.iter() {
+                let nanos = nanos.as_integer().filter(|n| *n >= 0).ok_or_else(|| {
+                    Error::Format(format!("Invalid timing for {}.{}", day.key, phase))
+                })?;
+                day_medians.insert(phase.clone(), Duration::from_nanos(nanos as u64));
+            }
+            medians.insert((day.year, day.day), day_medians);
+        }
+
+        Ok(Baseline { medians })
+    }
+}
+
+impl Baseline {
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let table = nest_day_tables(self.medians.iter().map(|(year_day, phases)| {
+            let phases = phases
+                .iter()
+                .map(|(phase, median)| (phase.clone(), Value::Integer(median.as_nanos() as i64)))
+                .collect();
+
+            (*year_day, phases)
+        }));
+
+        fs::write(path, Value::Table(table).to_string())?;
+
+        Ok(())
+    }
+
+    /// Replace the stored medians for the benchmarked day with the new results
+    pub fn record(&mut self, result: &BenchResult) {
+        self.medians.insert(
+            (result.year, result.day),
+            result
+                .phases()
+                .iter()
+                .map(|(phase, stats)| (phase.to_string(), stats.median))
+                .collect(),
+        );
+    }
+
+    /// Compare each phase's median against the baseline, flagging any that slowed by more than
+    /// `threshold` percent. Phases without a baseline are skipped.
+    pub fn compare(&self, result: &BenchResult, threshold: f64) -> Vec<PhaseComparison> {
+        result
+            .phases()
+            .iter()
+            .filter_map(|(phase, stats)| {
+                let baseline = *self.medians.get(&(result.year, result.day))?.get(*phase)?;
+                let change = if baseline.as_nanos() == 0 {
+                    0.0
+                } else {
+                    (stats.median.as_secs_f64() - baseline.as_secs_f64()) / baseline.as_secs_f64()
+                        * 100.0
+                };
+
+                Some(PhaseComparison {
+                    day: result.day,
+                    phase,
+                    baseline,
+                    current: stats.median,
+                    change,
+                    regressed: change > threshold,
+                })
+            })
+            .collect()
+    }
+}
+
+/// What to do with a baseline after benchmarking
+#[derive(Debug, Default)]
+pub struct BaselineOptions {
+    /// The file to record the medians in, keeping those of any days that weren't run
+    pub save: Option<PathBuf>,
+    /// The baseline to compare the medians with
+    pub compare: Option<Baseline>,
+    /// The slowdown, as a percentage, allowed before a phase counts as a regression
+    pub threshold: f64,
+}
+
+impl BaselineOptions {
+    /// Print how the results compare with the baseline and record them in the baseline file,
+    /// returning whether none of the phases regressed. Fails if the file can't be updated.
+    pub fn apply(&self, results: &[BenchResult]) -> Result<bool> {
+        let passed = match &self.compare {
+            Some(baseline) => print_comparisons(
+                &results
+                    .iter()
+                    .flat_map(|r| baseline.compare(r, self.threshold))
+                    .collect::<Vec<_>>(),
+            ),
+            None => true,
+        };
+
+        if let Some(path) = &self.save {
+            let mut baseline = Baseline::load_or_default(path)?;
+            results.iter().for_each(|r| baseline.record(r));
+            baseline.save(path)?;
+        }
+
+        Ok(passed)
+    }
+}
+
+/// Print each comparison, returning whether none of the phases regressed
+pub fn print_comparisons(comparisons: &[PhaseComparison]) -> bool {
+    for c in comparisons {
+        println!(
+            "Day {} {}: {:.2?} -> {:.2?} ({:+.1}%){}",
+            c.day,
+            c.phase,
+            c.baseline,
+            c.current,
+            c.change,
+            if c.regressed { " REGRESSED" } else { "" }
+        );
+    }
+
+    !comparisons.iter().any(|c| c.regressed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bench::Stats;
+
+    fn bench(year: u32, day: u32, setup_micros: u64) -> BenchResult {
+        let stats = |micros| Stats {
+            median: Duration::from_micros(micros),
+            ..Stats::default()
+        };
+
+        BenchResult {
+            year,
+            day,
+            iterations: 10,
+            warmup: 0,
+            setup: stats(setup_micros),
+            part_1: stats(100),
+            part_2: stats(100),
+            memory: None,
+        }
+    }
+
+    #[test]
+    fn flag_slowdowns_over_the_threshold() {
+        let baseline = Baseline::parse("[day_1]\nsetup = 100000\npart_1 = 100000").unwrap();
+
+        let within = baseline.compare(&bench(2020, 1, 110), 10.0);
+        assert_eq!(within.len(), 2);
+        assert!((within[0].change - 10.0).abs() < 1e-9);
+        assert!(!within[0].regressed);
+
+        let over = baseline.compare(&bench(2020, 1, 111), 10.0);
+        assert_eq!(over[0].phase, "setup");
+        assert!(over[0].regressed);
+        assert!(!over[1].regressed);
+
+        assert!(baseline.compare(&bench(2020, 2, 500), 10.0).is_empty());
+    }
+
+    #[test]
+    fn zero_baseline_never_regresses() {
+        let baseline = Baseline::parse("[day_1]\nsetup = 0").unwrap();
+        let comparisons = baseline.compare(&bench(2020, 1, 50), 10.0);
+
+        assert_eq!(comparisons.len(), 1);
+        assert_eq!(comparisons[0].change, 0.0);
+        assert!(!comparisons[0].regressed);
+    }
+
+    #[test]
+    fn round_trip_recorded_medians() {
+        let path = std::env::temp_dir().join(format!("aoc2020-baseline-{}", std::process::id()));
+        let mut baseline = Baseline::default();
+        baseline.record(&bench(2020, 3, 250));
+        baseline.record(&bench(2019, 3, 400));
+        baseline.save(&path).unwrap();
+
+        let loaded = Baseline::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            loaded.compare(&bench(2020, 3, 250), 0.0)[0].baseline,
+            Duration::from_micros(250)
+        );
+        assert_eq!(
+            loaded.compare(&bench(2019, 3, 250), 0.0)[0].baseline,
+            Duration::from_micros(400)
+        );
+        assert!(Baseline::parse("[day_1]\nsetup = -5").is_err());
+    }
+}
@@ -0,0 +1,341 @@
+use std::time::{Duration, Instant};
+
+use crate::alloc::{self, format_bytes};
+use crate::baseline::BaselineOptions;
+use crate::days::{configured_challenge, Params};
+use crate::error::Result;
+use crate::runner::Memory;
+
+#[derive(Debug, Clone, Copy)]
+pub struct BenchOptions {
+    pub iterations: usize,
+    pub warmup: usize,
+}
+
+/// Summary statistics over the samples left after outlier rejection
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+    pub stddev: Duration,
+    pub outliers: usize,
+}
+
+#[derive(Debug)]
+pub struct BenchResult {
+    pub year: u32,
+    pub day: u32,
+    pub iterations: usize,
+    pub warmup: usize,
+    pub setup: Stats,
+    pub part_1: Stats,
+    pub part_2: Stats,
+    /// The allocations made by each phase of the last iteration, if the counting allocator is
+    /// installed
+    pub memory: Option<Memory>,
+}
+
+impl BenchResult {
+    pub fn phases(&self) -> [(&'static str, &Stats); 3] {
+        [
+            ("setup", &self.setup),
+            ("part_1", &self.part_1),
+            ("part_2", &self.part_2),
+        ]
+    }
+}
+
+/// The value at the given percentile of a sorted, non-empty slice, using the nearest rank
+fn percentile(sorted: &[f64], pct: f64) -> f64 {
+    let rank = ((pct / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+impl Stats {
+    /// Reject samples outside the Tukey fences (1.5 IQR beyond the quartiles) and summarise the
+    /// rest
+    pub fn from_samples(samples: &[Duration]) -> Stats {
+        let mut secs: Vec<f64> = samples.iter().map(|d| d.as_secs_f64()).collect();
+        secs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        if secs.is_empty() {
+            return Stats::default();
+        }
+
+        let (q1, q3) = (percentile(&secs, 25.0), percentile(&secs, 75.0));
+        let fence = 1.5 * (q3 - q1);
+        let kept: Vec<f64> = secs
+            .iter()
+            .cloned()
+            .filter(|&s| s >= q1 - fence && s <= q3 + fence)
+            .collect();
+
+        let n = kept.len() as f64;
+        let mean = kept.iter().sum::<f64>() / n;
+        let variance = if kept.len() > 1 {
+            kept.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (n - 1.0)
+        } else {
+            0.0
+        };
+        let median = if kept.len().is_multiple_of(2) {
+            (kept[kept.len() / 2 - 1] + kept[kept.len() / 2]) / 2.0
+        } else {
+            kept[kept.len() / 2]
+        };
+
+        Stats {
+            min: Duration::from_secs_f64(kept[0]),
+            median: Duration::from_secs_f64(median),
+            mean: Duration::from_secs_f64(mean),
+            p95: Duration::from_secs_f64(percentile(&kept, 95.0)),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+            outliers: secs.len() - kept.len(),
+        }
+    }
+}
+
+/// Build and run the day's full lifecycle repeatedly, timing each phase with a monotonic clock
+pub fn bench_day(
+    year: u32,
+    day: u32,
+    input: &str,
+    params: &Params,
+    options: BenchOptions,
+) -> Result<BenchResult> {
+    let mut setup = Vec::with_capacity(options.iterations);
+    let mut part_1 = Vec::with_capacity(options.iterations);
+    let mut part_2 = Vec::with_capacity(options.iterations);
+    let mut memory = Memory::default();
+
+    for i in 0..(options.warmup + options.iterations) {
+        let mut c = configured_challenge(year, day, input, params)?;
+
+        alloc::start_phase();
+        let start_time = Instant::now();
+        c.setup()?;
+        let setup_time = start_time.elapsed();
+        memory.setup = alloc::end_phase();
+
+        alloc::start_phase();
+        let start_time = Instant::now();
+        c.part_1()?;
+        let part_1_time = start_time.elapsed();
+        memory.part_1 = alloc::end_phase();
+
+        alloc::start_phase();
+        let start_time = Instant::now();
+        c.part_2()?;
+        let part_2_time = start_time.elapsed();
+        memory.part_2 = alloc::end_phase();
+
+        if i >= options.warmup {
+            setup.push(setup_time);
+            part_1.push(part_1_time);
+            part_2.push(part_2_time);
+        }
+    }
+
+    Ok(BenchResult {
+        year,
+        day,
+        iterations: options.iterations,
+        warmup: options.warmup,
+        setup: Stats::from_samples(&setup),
+        part_1: Stats::from_samples(&part_1),
+        part_2: Stats::from_samples(&part_2),
+        memory: if alloc::is_counting() {
+            Some(memory)
+        } else {
+            None
+        },
+    })
+}
+
+/// Print the statistics of each phase, with the peak and total memory of its last iteration when
+/// allocations were counted
+pub fn print_bench(result: &BenchResult) {
+    println!(
+        "Day {} ({} iterations after {} warm-up)",
+        result.day, result.iterations, result.warmup
+    );
+    print!(
+        "{:>8} | {:>12} | {:>12} | {:>12} | {:>12} | {:>12} | {:>8}",
+        "Phase", "Min", "Median", "Mean", "p95", "Std dev", "Outliers"
+    );
+    if result.memory.is_some() {
+        print!(" | {:>10} | {:>10}", "Peak", "Allocated");
+    }
+    println!();
+
+    let memory = result.memory.map(|m| [m.setup, m.part_1, m.part_2]);
+    for (i, (name, stats)) in result.phases().iter().enumerate() {
+        print!(
+            "{:>8} | {:>12} | {:>12} | {:>12} | {:>12} | {:>12} | {:>8}",
+            name,
+            format!("{:.2?}", stats.min),
+            format!("{:.2?}", stats.median),
+            format!("{:.2?}", stats.mean),
+            format!("{:.2?}", stats.p95),
+            format!("{:.2?}", stats.stddev),
+            stats.outliers
+        );
+        if let Some(memory) = memory {
+            print!(
+                " | {:>10} | {:>10}",
+                format_bytes(memory[i].peak),
+                format_bytes(memory[i].total)
+            );
+        }
+        println!();
+    }
+}
+
+/// Benchmark each day whose input could be read, printing its statistics as soon as it's done,
+/// then compare the results with a baseline or record them as `baselines` asks. Returns whether
+/// every day was benchmarked without regressing.
+pub fn bench_days(
+    year: u32,
+    inputs: Vec<(u32, Result<String>)>,
+    params: &Params,
+    options: BenchOptions,
+    baselines: &BaselineOptions,
+) -> bool {
+    let mut passed = true;
+    let mut results = Vec::new();
+
+    for (day, input) in inputs {
+        match input.and_then(|s| bench_day(year, day, &s, params, options)) {
+            Ok(result) => {
+                print_bench(&result);
+                results.push(result);
+            }
+            Err(err) => {
+                eprintln!("Failed to benchmark day {}: {}", day, err);
+                passed = false;
+            }
+        }
+        println!();
+    }
+
+    match baselines.apply(&results) {
+        Ok(no_regressions) => passed && no_regressions,
+        Err(err) => {
+            eprintln!("Failed to save the baseline: {}", err);
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::baseline::Baseline;
+    use crate::error::Error;
+    use crate::toml_file::TomlFile;
+
+    fn secs(samples: &[u64]) -> Vec<Duration> {
+        samples.iter().map(|&s| Duration::from_secs(s)).collect()
+    }
+
+    #[test]
+    fn nearest_rank_percentiles() {
+        let sorted = [10.0, 20.0, 30.0, 40.0, 50.0];
+
+        assert_eq!(percentile(&sorted, 0.0), 10.0);
+        assert_eq!(percentile(&sorted, 20.0), 10.0);
+        assert_eq!(percentile(&sorted, 21.0), 20.0);
+        assert_eq!(percentile(&sorted, 50.0), 30.0);
+        assert_eq!(percentile(&sorted, 95.0), 50.0);
+        assert_eq!(percentile(&sorted, 100.0), 50.0);
+        assert_eq!(percentile(&[7.0], 95.0), 7.0);
+    }
+
+    #[test]
+    fn single_sample() {
+        let stats = Stats::from_samples(&secs(&[5]));
+
+        assert_eq!(stats.min, Duration::from_secs(5));
+        assert_eq!(stats.median, Duration::from_secs(5));
+        assert_eq!(stats.mean, Duration::from_secs(5));
+        assert_eq!(stats.p95, Duration::from_secs(5));
+        assert_eq!(stats.stddev, Duration::from_secs(0));
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn even_number_of_samples() {
+        let stats = Stats::from_samples(&secs(&[4, 1, 3, 2]));
+
+        assert_eq!(stats.min, Duration::from_secs(1));
+        assert_eq!(stats.median, Duration::from_millis(2500));
+        assert_eq!(stats.mean, Duration::from_millis(2500));
+        assert_eq!(stats.p95, Duration::from_secs(4));
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn reject_outliers() {
+        let stats = Stats::from_samples(&secs(&[100, 1, 2, 3, 4, 5, 6, 7, 8, 9]));
+
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.min, Duration::from_secs(1));
+        assert_eq!(stats.median, Duration::from_secs(5));
+        assert_eq!(stats.mean, Duration::from_secs(5));
+        assert_eq!(stats.p95, Duration::from_secs(9));
+        assert!((stats.stddev.as_secs_f64() - 7.5f64.sqrt()).abs() < 1e-6);
+
+        let low = Stats::from_samples(&secs(&[0, 50, 51, 52, 53, 54, 55, 56]));
+        assert_eq!(low.outliers, 1);
+        assert_eq!(low.min, Duration::from_secs(50));
+    }
+
+    #[test]
+    fn no_samples() {
+        let stats = Stats::from_samples(&[]);
+
+        assert_eq!(stats.median, Duration::default());
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn bench_days_against_a_baseline() {
+        let path = std::env::temp_dir().join(format!("aoc2020-bench-{}", std::process::id()));
+        let options = BenchOptions {
+            iterations: 2,
+            warmup: 0,
+        };
+        let inputs = || vec![(1, Ok("1721\n979\n366\n299\n675\n1456\n".to_string()))];
+
+        let save = BaselineOptions {
+            save: Some(path.clone()),
+            ..BaselineOptions::default()
+        };
+        assert!(bench_days(2020, inputs(), &Params::new(), options, &save));
+        let saved = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(saved.contains("[2020.day_1]"));
+
+        let strict = BaselineOptions {
+            compare: Some(Baseline::parse("[day_1]\nsetup = 1\npart_1 = 1\npart_2 = 1").unwrap()),
+            ..BaselineOptions::default()
+        };
+        assert!(!bench_days(
+            2020,
+            inputs(),
+            &Params::new(),
+            options,
+            &strict
+        ));
+
+        let missing = vec![(1, Err(Error::no_solution("no input")))];
+        assert!(!bench_days(
+            2020,
+            missing,
+            &Params::new(),
+            options,
+            &BaselineOptions::default()
+        ));
+    }
+}
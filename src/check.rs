@@ -0,0 +1,204 @@
+use std::collections::HashMap;
+
+use toml::Value;
+
+use crate::error::{Error, Result};
+use crate::runner::RunResult;
+use crate::toml_file::{day_tables, parse_table, TomlFile};
+
+/// Expected answers keyed by year, day and part, loaded from a TOML file such as
+///
+/// ```toml
+/// [2020.day_1]
+/// part_1 = 514579
+/// part_2 = "241861950"
+/// ```
+///
+/// Days given at the top level, as in `[day_1]`, are from `DEFAULT_YEAR`.
+#[derive(Debug, Default)]
+pub struct ExpectedAnswers {
+    answers: HashMap<(u32, u32, u8), String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Pass,
+    Fail { expected: String, actual: String },
+    Unknown,
+}
+
+#[derive(Debug, Clone)]
+pub struct PartCheck {
+    pub day: u32,
+    pub part: u8,
+    pub outcome: Outcome,
+}
+
+fn render_value(value: &Value) -> Result<String> {
+    match value {
+        Value::Integer(v) => Ok(v.to_string()),
+        Value::String(s) => Ok(s.clone()),
+        Value::Array(items) => Ok(format!(
+            "[{}]",
+            items
+                .iter()
+                .map(render_value)
+                .collect::<Result<Vec<String>>>()?
+                .join(", ")
+        )),
+        v => Err(Error::Format(format!("Unsupported answer value {}", v))),
+    }
+}
+
+impl TomlFile for ExpectedAnswers {
+    fn parse(s: &str) -> Result<Self> {
+        let table = parse_table(s, "days")?;
+
+        let mut answers = HashMap::new();
+        for day in day_tables(&table)? {
+            for (part_key, value) in day.table.iter() {
+                let part = match part_key.as_str() {
+                    "part_1" => 1,
+                    "part_2" => 2,
+                    k => return Err(Error::Format(format!("Invalid part key \"{}\"", k))),
+                };
+                answers.insert((day.year, day.day, part), render_value(value)?);
+            }
+        }
+
+        Ok(ExpectedAnswers { answers })
+    }
+}
+
+impl ExpectedAnswers {
+    pub fn expected(&self, year: u32, day: u32, part: u8) -> Option<&str> {
+        self.answers.get(&(year, day, part)).map(|s| s.as_str())
+    }
+
+    /// Check the answer to each part that was run
+    pub fn check(&self, result: &RunResult) -> Vec<PartCheck> {
+        vec![(1, &result.part_1), (2, &result.part_2)]
+            .into_iter()
+            .filter_map(|(part, answer)| answer.as_ref().map(|a| (part, a)))
+            .map(|(part, answer)| {
+                let actual = answer.to_string();
+                let outcome = match self.expected(result.year, result.day, part) {
+                    Some(expected) if expected == actual => Outcome::Pass,
+                    Some(expected) => Outcome::Fail {
+                        expected: expected.to_string(),
+                        actual,
+                    },
+                    None => Outcome::Unknown,
+                };
+
+                PartCheck {
+                    day: result.day,
+                    part,
+                    outcome,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Print the outcome of each check, returning whether none of them failed
+pub fn print_checks(checks: &[PartCheck]) -> bool {
+    for check in checks {
+        match &check.outcome {
+            Outcome::Pass => println!("Day {} part {}: ok", check.day, check.part),
+            Outcome::Unknown => {
+                println!("Day {} part {}: no expected answer", check.day, check.part)
+            }
+            Outcome::Fail { expected, actual } => println!(
+                "Day {} part {}: FAILED\n    expected: {}\n    actual:   {}",
+                check.day, check.part, expected, actual
+            ),
+        }
+    }
+
+    !checks
+        .iter()
+        .any(|c| matches!(c.outcome, Outcome::Fail { .. }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::Answer;
+
+    fn run(year: u32, day: u32, part_1: Option<Answer>, part_2: Option<Answer>) -> RunResult {
+        RunResult::with_answers(year, day, part_1, part_2, [0; 3])
+    }
+
+    fn outcomes(checks: Vec<PartCheck>) -> Vec<(u8, Outcome)> {
+        checks.into_iter().map(|c| (c.part, c.outcome)).collect()
+    }
+
+    #[test]
+    fn parse_values() {
+        let answers = ExpectedAnswers::parse(
+            "[day_1]\npart_1 = 514579\npart_2 = \"abc\"\n[2019.day_3]\npart_1 = [1, \"x\", [2]]\n",
+        )
+        .unwrap();
+
+        assert_eq!(answers.expected(2020, 1, 1), Some("514579"));
+        assert_eq!(answers.expected(2020, 1, 2), Some("abc"));
+        assert_eq!(answers.expected(2019, 3, 1), Some("[1, x, [2]]"));
+        assert_eq!(answers.expected(2019, 1, 1), None);
+        assert_eq!(answers.expected(2020, 3, 1), None);
+    }
+
+    #[test]
+    fn reject_invalid_files() {
+        for file in &[
+            "[day_x]\npart_1 = 1",
+            "[day_1]\npart_3 = 1",
+            "[day_1]\nanswer = 1",
+            "[2019.day_1]\npart_0 = 1",
+            "[day_1]\npart_1 = 1.5",
+            "[day_1]\npart_1 = [1, 2.5]",
+            "[day_1]\npart_1 = true",
+            "day_1 = 5",
+            "[day_1\npart_1 = 1",
+        ] {
+            assert!(
+                matches!(ExpectedAnswers::parse(file), Err(Error::Format(_))),
+                "{:?} should be rejected",
+                file
+            );
+        }
+    }
+
+    #[test]
+    fn check_outcomes() {
+        let answers =
+            ExpectedAnswers::parse("[day_1]\npart_1 = 7\n[2019.day_1]\npart_2 = [1, 2]\n").unwrap();
+
+        assert_eq!(
+            outcomes(answers.check(&run(2020, 1, Some(Answer::from(7)), Some(Answer::from(8))))),
+            vec![(1, Outcome::Pass), (2, Outcome::Unknown)]
+        );
+        assert_eq!(
+            outcomes(answers.check(&run(
+                2019,
+                1,
+                Some(Answer::from(7)),
+                Some(Answer::from(vec![1, 3]))
+            ))),
+            vec![
+                (1, Outcome::Unknown),
+                (
+                    2,
+                    Outcome::Fail {
+                        expected: "[1, 2]".to_string(),
+                        actual: "[1, 3]".to_string()
+                    }
+                )
+            ]
+        );
+        assert_eq!(
+            outcomes(answers.check(&run(2019, 1, None, Some(Answer::from(vec![1, 2]))))),
+            vec![(2, Outcome::Pass)]
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use std::path::PathBuf;
+
+use toml::Value;
+
+use crate::error::{Error, Result};
+use crate::output::Format;
+use crate::toml_file::{parse_table, sub_table, TomlFile};
+
+pub const DEFAULT_CONFIG: &str = "aoc.toml";
+
+/// Project defaults loaded from a TOML file such as
+///
+/// ```toml
+/// year = 2020
+/// inputs = "inputs/{year}/day_{day}.txt"
+/// format = "text"
+/// check = "answers.toml"
+///
+/// [bench]
+/// iterations = 100
+/// warmup = 3
+/// ```
+///
+/// Every setting is optional, and the matching command line flag takes precedence.
+#[derive(Debug, Default, PartialEq)]
+pub struct Config {
+    /// The year to run when `--year` isn't given
+    pub year: Option<u32>,
+    /// Where each day's input is cached, with `{year}` and `{day}` standing in for the puzzle's
+    /// year and day
+    pub inputs: Option<String>,
+    pub format: Option<Format>,
+    /// The expected answers to check every run against
+    pub check: Option<PathBuf>,
+    pub bench_iterations: Option<usize>,
+    pub bench_warmup: Option<usize>,
+    pub base_url: Option<String>,
+    pub session_file: Option<PathBuf>,
+}
+
+fn string(key: &str, value: &Value) -> Result<String> {
+    value
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| Error::Format(format!("\"{}\" must be a string", key)))
+}
+
+fn count(key: &str, value: &Value) -> Result<usize> {
+    value
+        .as_integer()
+        .filter(|v| *v >= 0)
+        .map(|v| v as usize)
+        .ok_or_else(|| Error::Format(format!("\"{}\" must be a non-negative integer", key)))
+}
+
+impl TomlFile for Config {
+    fn parse(s: &str) -> Result<Self> {
+        let table = parse_table(s, "settings")?;
+
+        let mut config = Config::default();
+        for (key, value) in table.iter() {
+            match key.as_str() {
+                "year" => config.year = Some(count(key, value)? as u32),
+                "inputs" => config.inputs = Some(string(key, value)?),
+                "format" => {
+                    config.format = Some(string(key, value)?.parse().map_err(Error::Format)?)
+                }
+                "check" => config.check = Some(PathBuf::from(string(key, value)?)),
+                "base_url" => config.base_url = Some(string(key, value)?),
+                "session_file" => config.session_file = Some(PathBuf::from(string(key, value)?)),
+                "bench" => {
+                    for (key, value) in sub_table(key, value)?.iter() {
+                        match key.as_str() {
+                            "iterations" => config.bench_iterations = Some(count(key, value)?),
+                            "warmup" => config.bench_warmup = Some(count(key, value)?),
+                            k => {
+                                return Err(Error::Format(format!(
+                                    "Unknown setting \"bench.{}\"",
+                                    k
+                                )))
+                            }
+                        }
+                    }
+                }
+                k => return Err(Error::Format(format!("Unknown setting \"{}\"", k))),
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_every_setting() {
+        let config = Config::parse(
+            r#"
+            year = 2019
+            inputs = "puzzles/{day}/input.txt"
+            format = "json"
+            check = "answers.toml"
+            base_url = "http://localhost:8080"
+            session_file = "session.txt"
+
+            [bench]
+            iterations = 50
+            warmup = 5
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config,
+            Config {
+                year: Some(2019),
+                inputs: Some("puzzles/{day}/input.txt".to_string()),
+                format: Some(Format::Json),
+                check: Some(PathBuf::from("answers.toml")),
+                bench_iterations: Some(50),
+                bench_warmup: Some(5),
+                base_url: Some("http://localhost:8080".to_string()),
+                session_file: Some(PathBuf::from("session.txt")),
+            }
+        );
+    }
+
+    #[test]
+    fn reject_invalid_settings() {
+        assert!(Config::parse("input = \"inputs\"").is_err());
+        assert!(Config::parse("format = \"yaml\"").is_err());
+        assert!(Config::parse("[bench]\niterations = -1").is_err());
+    }
+}
@@ -1,43 +1,344 @@
-use std::path::Path;
-
-use day_1::Day1;
-use day_2::Day2;
-use day_3::Day3;
-use day_4::Day4;
-use day_5::Day5;
-use day_6::Day6;
-use day_8::Day8;
-use day_10::Day10;
-
-pub mod day_1;
-pub mod day_2;
-pub mod day_3;
-pub mod day_4;
-pub mod day_5;
-pub mod day_6;
-pub mod day_8;
-pub mod day_10;
-
-pub trait Challenge<'a> {
-    fn setup(&mut self);
-    fn part_1(&mut self);
-    fn part_2(&mut self);
-    fn format_answers(&self) -> String;
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::fmt::{self, Debug, Display};
+use std::str::FromStr;
+
+use crate::error::{Error, Result};
+
+/// The answer produced by a single part of a challenge. Integers are built with `From`, which
+/// only uses `BigInteger` for values outside the `i64` range so that equal answers compare equal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Integer(i64),
+    /// An integer too large for an `i64`
+    BigInteger(i128),
+    Text(String),
+    List(Vec<Answer>),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Integer(v) => write!(f, "{}", v),
+            Answer::BigInteger(v) => write!(f, "{}", v),
+            Answer::Text(s) => write!(f, "{}", s),
+            Answer::List(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+macro_rules! answer_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(v: $t) -> Self {
+                    Answer::Integer(v.into())
+                }
+            }
+        )*
+    };
+}
+
+answer_from_int!(i32, i64, u16, u32);
+
+impl From<usize> for Answer {
+    fn from(v: usize) -> Self {
+        Answer::Integer(v as i64)
+    }
+}
+
+impl From<i128> for Answer {
+    fn from(v: i128) -> Self {
+        i64::try_from(v).map_or(Answer::BigInteger(v), Answer::Integer)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+impl<T: Into<Answer>> From<Vec<T>> for Answer {
+    fn from(items: Vec<T>) -> Self {
+        Answer::List(items.into_iter().map(|i| i.into()).collect())
+    }
+}
+
+/// A day's puzzle, built from its input. `setup` parses the input and must be called before
+/// either part is solved. Challenges are `Send` so that days can be run on separate threads.
+pub trait Challenge<'a>: Send {
+    /// Apply the parameters given for this run before `setup`. Days without parameters reject
+    /// any they're given.
+    fn configure(&mut self, params: &Params) -> Result<()> {
+        params.expect_only(&[])
+    }
+    fn setup(&mut self) -> Result<()>;
+    /// The structure `setup` parsed the input into
+    fn parsed(&self) -> &dyn Debug;
+    /// Problems `setup` worked around instead of failing, such as skipped input lines
+    fn warnings(&self) -> Vec<String> {
+        Vec::new()
+    }
+    fn part_1(&mut self) -> Result<Answer>;
+    fn part_2(&mut self) -> Result<Answer>;
+}
+
+/// Named values that change what a day solves, such as Day 1's `target`, given on the command
+/// line as `--param key=value`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Params(BTreeMap<String, String>);
+
+impl Params {
+    pub fn new() -> Self {
+        Params::default()
+    }
+
+    pub fn insert(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.0.insert(key.into(), value.into());
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Parse a `key=value` pair and add it
+    pub fn insert_pair(&mut self, pair: &str) -> Result<()> {
+        let (key, value) = pair
+            .split_once('=')
+            .filter(|(key, _)| !key.is_empty())
+            .ok_or_else(|| Error::Param(format!("\"{}\" is not of the form key=value", pair)))?;
+        self.insert(key, value);
+
+        Ok(())
+    }
+
+    /// The parameter's value, or `None` if it wasn't given
+    pub fn get<T>(&self, key: &str) -> Result<Option<T>>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.0
+            .get(key)
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|e| Error::Param(format!("{} = \"{}\": {}", key, value, e)))
+            })
+            .transpose()
+    }
+
+    /// Reject any parameter not among `known`
+    pub fn expect_only(&self, known: &[&str]) -> Result<()> {
+        match self.0.keys().find(|key| !known.contains(&key.as_str())) {
+            Some(key) => Err(Error::Param(format!("\"{}\" is not supported", key))),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A day's metadata and constructor. Each day module exports one as `REGISTRATION`, which
+/// `register_days!` collects into its year's `REGISTRY`.
+pub struct Registration {
+    pub day: u32,
+    pub title: &'static str,
+    pub build: for<'a> fn(&'a str) -> Box<dyn Challenge<'a> + 'a>,
+}
+
+/// Declare each day's module in a year's module tree and add its registration to the year's
+/// `REGISTRY`
+macro_rules! register_days {
+    ($($module:ident),* $(,)?) => {
+        $(pub mod $module;)*
+
+        /// Every implemented day of the year
+        pub static REGISTRY: &[$crate::days::Registration] = &[$($module::REGISTRATION),*];
+    };
 }
 
+/// Declare each year's module tree and add its registry to `YEARS`. Each year's module
+/// exports its `YEAR` and the `REGISTRY` built by `register_days!`.
+macro_rules! register_years {
+    ($($module:ident),* $(,)?) => {
+        $(pub mod $module;)*
+
+        /// Each year's registry of implemented days
+        pub static YEARS: &[(u32, &[$crate::days::Registration])] =
+            &[$(($module::YEAR, $module::REGISTRY)),*];
+    };
+}
+
+/// The year run when none is given
+pub const DEFAULT_YEAR: u32 = 2020;
+
+pub fn registration(year: u32, day: u32) -> Option<&'static Registration> {
+    crate::YEARS
+        .iter()
+        .filter(|(y, _)| *y == year)
+        .flat_map(|(_, registry)| registry.iter())
+        .find(|r| r.day == day)
+}
+
+/// Build the challenge for a year's day from its puzzle input
 pub fn challenge_from_day<'a>(
-    day: &u32,
-    file_path: &'a Path,
-) -> Result<Box<dyn Challenge<'a> + 'a>, String> {
-    match day {
-        1 => Ok(Day1::build(file_path)),
-        2 => Ok(Day2::build(file_path)),
-        3 => Ok(Day3::build(file_path)),
-        4 => Ok(Day4::build(file_path)),
-        5 => Ok(Day5::build(file_path)),
-        6 => Ok(Day6::build(file_path)),
-        8 => Ok(Day8::build(file_path)),
-        10 => Ok(Day10::build(file_path)),
-        _ => Err(format!("Day {} is not implemented", day)),
+    year: u32,
+    day: u32,
+    input: &'a str,
+) -> Result<Box<dyn Challenge<'a> + 'a>> {
+    registration(year, day)
+        .map(|r| (r.build)(input))
+        .ok_or(Error::NotImplemented(year, day))
+}
+
+/// Build the challenge for a year's day and configure it with the parameters for the run
+pub fn configured_challenge<'a>(
+    year: u32,
+    day: u32,
+    input: &'a str,
+    params: &Params,
+) -> Result<Box<dyn Challenge<'a> + 'a>> {
+    let mut challenge = challenge_from_day(year, day, input)?;
+    challenge.configure(params)?;
+
+    Ok(challenge)
+}
+
+/// Every year with a registry, in ascending order
+pub fn implemented_years() -> Vec<u32> {
+    let mut years: Vec<u32> = crate::YEARS.iter().map(|(y, _)| *y).collect();
+    years.sort_unstable();
+
+    years
+}
+
+/// Every implemented day of the year, in ascending order
+pub fn implemented_days(year: u32) -> Vec<u32> {
+    let mut days: Vec<u32> = crate::YEARS
+        .iter()
+        .filter(|(y, _)| *y == year)
+        .flat_map(|(_, registry)| registry.iter().map(|r| r.day))
+        .collect();
+    days.sort_unstable();
+
+    days
+}
+
+/// Run a day from setup through both parts, for testing it against the puzzle's examples
+#[cfg(test)]
+pub(crate) fn run_example(year: u32, day: u32, input: &str) -> Result<(Answer, Answer)> {
+    run_example_with(year, day, input, &Params::new())
+}
+
+/// Run a day from setup through both parts with the given parameters
+#[cfg(test)]
+pub(crate) fn run_example_with(
+    year: u32,
+    day: u32,
+    input: &str,
+    params: &Params,
+) -> Result<(Answer, Answer)> {
+    let mut challenge = configured_challenge(year, day, input, params)?;
+    challenge.setup()?;
+
+    Ok((challenge.part_1()?, challenge.part_2()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalise_integers() {
+        assert_eq!(Answer::from(8i128), Answer::from(8));
+        assert_eq!(Answer::from(8i128), Answer::Integer(8));
+        assert_eq!(
+            Answer::from(i128::from(i64::MIN) - 1),
+            Answer::BigInteger(-9_223_372_036_854_775_809)
+        );
+    }
+
+    #[test]
+    fn parse_key_value_pairs() {
+        let mut params = Params::new();
+        params.insert_pair("target=10").unwrap();
+        params.insert_pair("policy=a=b").unwrap();
+        params.insert_pair("empty=").unwrap();
+
+        assert_eq!(params.get::<i64>("target").unwrap(), Some(10));
+        assert_eq!(
+            params.get::<String>("policy").unwrap(),
+            Some("a=b".to_string())
+        );
+        assert_eq!(params.get::<String>("empty").unwrap(), Some(String::new()));
+        assert_eq!(params.get::<i64>("k").unwrap(), None);
+        assert!(matches!(params.get::<bool>("target"), Err(Error::Param(_))));
+
+        for pair in &["=x", "novalue", ""] {
+            assert!(
+                matches!(params.insert_pair(pair), Err(Error::Param(_))),
+                "accepted \"{}\"",
+                pair
+            );
+        }
+    }
+
+    #[test]
+    fn reject_unknown_params() {
+        let mut params = Params::new();
+        assert!(params.expect_only(&[]).is_ok());
+
+        params.insert("target", "10");
+        assert!(params.expect_only(&["target", "k"]).is_ok());
+        assert!(matches!(params.expect_only(&["k"]), Err(Error::Param(_))));
+    }
+
+    #[test]
+    fn registered_days() {
+        assert_eq!(implemented_days(2020), vec![1, 2, 3, 4, 5, 6, 8, 10]);
+        assert!(implemented_days(2019).is_empty());
+
+        assert_eq!(
+            registration(2020, 8).map(|r| r.title),
+            Some("Handheld Halting")
+        );
+        assert!(registration(2020, 7).is_none());
+        assert!(registration(2019, 1).is_none());
+    }
+
+    #[test]
+    fn parts_require_setup() {
+        for (year, registry) in crate::YEARS {
+            for r in registry.iter() {
+                let mut challenge = (r.build)("");
+                assert!(
+                    matches!(challenge.part_1(), Err(Error::SetupRequired)),
+                    "{} day {} part 1 ran before setup",
+                    year,
+                    r.day
+                );
+                assert!(
+                    matches!(challenge.part_2(), Err(Error::SetupRequired)),
+                    "{} day {} part 2 ran before setup",
+                    year,
+                    r.day
+                );
+            }
+        }
     }
 }
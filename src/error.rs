@@ -0,0 +1,90 @@
+use std::{convert::Infallible, fmt, io};
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    /// The input could not be read
+    Io(io::Error),
+    /// The input was read but is malformed at the given (1-indexed) position
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// The input could not be downloaded
+    Fetch(String),
+    /// A supporting file, such as an answers file, is malformed
+    Format(String),
+    /// A parameter is unknown to the day or has an invalid value
+    Param(String),
+    /// The input is well formed but has no answer for the puzzle
+    NoSolution(String),
+    /// An answer is too large to represent
+    Overflow(String),
+    /// A part was run before `setup` populated the challenge
+    SetupRequired,
+    /// The year and day have no registered challenge
+    NotImplemented(u32, u32),
+}
+
+impl Error {
+    pub fn parse(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Error::Parse {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    pub fn no_solution(message: impl Into<String>) -> Self {
+        Error::NoSolution(message.into())
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "Could not read the input: {}", e),
+            Error::Parse {
+                line,
+                column,
+                message,
+            } => write!(
+                f,
+                "Parse error at line {}, column {}: {}",
+                line, column, message
+            ),
+            Error::Fetch(message) => write!(f, "Could not download the input: {}", message),
+            Error::Format(message) => write!(f, "Invalid file format: {}", message),
+            Error::Param(message) => write!(f, "Invalid parameter: {}", message),
+            Error::NoSolution(message) => write!(f, "No solution found: {}", message),
+            Error::Overflow(message) => write!(f, "Arithmetic overflow: {}", message),
+            Error::SetupRequired => write!(f, "The challenge has not been set up"),
+            Error::NotImplemented(year, day) => {
+                write!(f, "Day {} of {} is not implemented", day, year)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<Infallible> for Error {
+    fn from(e: Infallible) -> Self {
+        match e {}
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
@@ -0,0 +1,275 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::{Error, Result};
+use crate::input::read_input;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+/// The environment variable holding the session cookie, which takes precedence over the
+/// session file
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// The session token from `AOC_SESSION`, or else the first line of the session file
+pub fn session_token(session_file: &Path) -> Option<String> {
+    env::var(SESSION_VAR)
+        .ok()
+        .or_else(|| fs::read_to_string(session_file).ok())
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// Downloads puzzle inputs from `{base_url}/{year}/day/{day}/input` and submits answers
+#[derive(Debug, Clone)]
+pub struct Fetcher {
+    base_url: String,
+    session: Option<String>,
+}
+
+impl Fetcher {
+    pub fn new(base_url: &str, session: Option<String>) -> Fetcher {
+        Fetcher {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+        }
+    }
+
+    fn session(&self) -> Result<&str> {
+        self.session.as_deref().ok_or_else(|| {
+            Error::Fetch(format!(
+                "no session token, set {} or write it to the session file",
+                SESSION_VAR
+            ))
+        })
+    }
+
+    fn send(&self, request: ureq::Request, form: Option<&[(&str, &str)]>) -> Result<String> {
+        let url = request.url().to_string();
+        let request = request
+            .set("Cookie", &format!("session={}", self.session()?))
+            .set("User-Agent", concat!("aoc2020/", env!("CARGO_PKG_VERSION")));
+        let response = match form {
+            Some(form) => request.send_form(form),
+            None => request.call(),
+        };
+
+        match response {
+            Ok(response) => Ok(response.into_string()?),
+            Err(ureq::Error::Status(code, response)) => Err(Error::Fetch(format!(
+                "{} returned {} {}",
+                url,
+                code,
+                response.status_text()
+            ))),
+            Err(err) => Err(Error::Fetch(err.to_string())),
+        }
+    }
+
+    pub fn fetch(&self, year: u32, day: u32) -> Result<String> {
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+
+        self.send(ureq::get(&url), None)
+    }
+
+    /// Post an answer to `{base_url}/{year}/day/{day}/answer`, returning the response page
+    pub fn submit_answer(&self, year: u32, day: u32, part: u8, answer: &str) -> Result<String> {
+        let url = format!("{}/{}/day/{}/answer", self.base_url, year, day);
+        let level = part.to_string();
+
+        self.send(
+            ureq::post(&url),
+            Some(&[("level", level.as_str()), ("answer", answer)]),
+        )
+    }
+}
+
+/// Puzzle inputs stored as files, downloading any that are missing. By default they are kept as
+/// `YEAR/day_N.txt` under one directory.
+#[derive(Debug, Clone)]
+pub struct InputCache {
+    pattern: String,
+    fetcher: Fetcher,
+}
+
+impl InputCache {
+    pub fn new(dir: &Path, fetcher: Fetcher) -> InputCache {
+        InputCache::with_pattern(
+            &dir.join("{year}").join("day_{day}.txt").to_string_lossy(),
+            fetcher,
+        )
+    }
+
+    /// Keep the inputs at the paths given by the pattern, with `{year}` and `{day}` replaced by
+    /// the puzzle's year and day
+    pub fn with_pattern(pattern: &str, fetcher: Fetcher) -> InputCache {
+        InputCache {
+            pattern: pattern.to_string(),
+            fetcher,
+        }
+    }
+
+    pub fn fetcher(&self) -> &Fetcher {
+        &self.fetcher
+    }
+
+    /// Where the day's input is cached
+    pub fn path(&self, year: u32, day: u32) -> PathBuf {
+        PathBuf::from(
+            self.pattern
+                .replace("{year}", &year.to_string())
+                .replace("{day}", &day.to_string()),
+        )
+    }
+
+    /// The day's input from the cache, fetching and storing it first if it isn't there
+    pub fn get(&self, year: u32, day: u32) -> Result<String> {
+        let path = self.path(year, day);
+        if path.exists() {
+            return read_input(&path);
+        }
+
+        let input = self.fetcher.fetch(year, day)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, &input)?;
+
+        Ok(input)
+    }
+}
+
+/// Serve a single HTTP response from a local port, returning the base URL and a handle that
+/// yields the request
+#[cfg(test)]
+pub(crate) fn serve_once(
+    status: &'static str,
+    body: &'static str,
+) -> (String, std::thread::JoinHandle<String>) {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+    let handle = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = String::new();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line.trim().is_empty() {
+                break;
+            }
+            if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                content_length = len.trim().parse().unwrap();
+            }
+            request.push_str(&line);
+        }
+        let mut content = vec![0; content_length];
+        reader.read_exact(&mut content).unwrap();
+        request.push_str(&String::from_utf8(content).unwrap());
+
+        write!(
+            stream,
+            "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        )
+        .unwrap();
+
+        request
+    });
+
+    (base_url, handle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc2020-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        dir
+    }
+
+    #[test]
+    fn fetch_sends_the_session_cookie() {
+        let (base_url, server) = serve_once("200 OK", "1721\n979\n");
+        let fetcher = Fetcher::new(&base_url, Some("abc123".to_string()));
+
+        assert_eq!(fetcher.fetch(2020, 1).unwrap(), "1721\n979\n");
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2020/day/1/input HTTP/1.1"));
+        assert!(request.contains("session=abc123"));
+    }
+
+    #[test]
+    fn fetch_reports_error_statuses() {
+        let (base_url, server) = serve_once("404 Not Found", "");
+        let fetcher = Fetcher::new(&base_url, Some("abc123".to_string()));
+
+        assert!(matches!(fetcher.fetch(2020, 25), Err(Error::Fetch(_))));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn fetch_requires_a_session() {
+        let fetcher = Fetcher::new("http://127.0.0.1:1", None);
+
+        assert!(matches!(fetcher.fetch(2020, 1), Err(Error::Fetch(_))));
+    }
+
+    #[test]
+    fn submit_posts_the_level_and_answer() {
+        let (base_url, server) =
+            serve_once("200 OK", "<article>That's the right answer!</article>");
+        let fetcher = Fetcher::new(&base_url, Some("abc123".to_string()));
+
+        assert!(fetcher
+            .submit_answer(2020, 1, 2, "241861950")
+            .unwrap()
+            .contains("right answer"));
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /2020/day/1/answer HTTP/1.1"));
+        assert!(request.ends_with("level=2&answer=241861950"));
+    }
+
+    #[test]
+    fn cache_stores_downloaded_inputs() {
+        let dir = cache_dir("cache");
+        let (base_url, server) = serve_once("200 OK", "abc\n");
+        let cache = InputCache::new(&dir, Fetcher::new(&base_url, Some("abc123".to_string())));
+
+        assert_eq!(cache.get(2020, 6).unwrap(), "abc\n");
+        server.join().unwrap();
+
+        // The server has gone, so this can only be answered from the cache
+        assert_eq!(cache.get(2020, 6).unwrap(), "abc\n");
+        assert_eq!(
+            fs::read_to_string(dir.join("2020").join("day_6.txt")).unwrap(),
+            "abc\n"
+        );
+        assert_eq!(cache.path(2019, 6), dir.join("2019").join("day_6.txt"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn cache_follows_the_pattern() {
+        let cache =
+            InputCache::with_pattern("puzzles/{year}/{day}/input.txt", Fetcher::new("", None));
+
+        assert_eq!(
+            cache.path(2019, 12),
+            PathBuf::from("puzzles/2019/12/input.txt")
+        );
+    }
+}
@@ -0,0 +1,21 @@
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+
+use crate::error::Result;
+
+/// Read a challenge input from a file, or from stdin when the path is `-`
+pub fn read_input(path: &Path) -> Result<String> {
+    if path == Path::new("-") {
+        read_from(io::stdin())
+    } else {
+        Ok(fs::read_to_string(path)?)
+    }
+}
+
+pub fn read_from(mut reader: impl Read) -> Result<String> {
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+
+    Ok(input)
+}
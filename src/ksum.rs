@@ -0,0 +1,235 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::ops::{Add, Sub};
+
+use itertools::Itertools;
+
+/// The numbers a k-sum can be solved over, with `Default` as zero
+pub trait Summable: Copy + Ord + Hash + Default + Add<Output = Self> + Sub<Output = Self> {}
+
+impl<T> Summable for T where T: Copy + Ord + Hash + Default + Add<Output = T> + Sub<Output = T> {}
+
+/// Find `k` of the numbers, each used at most once, that sum to `target`, returned in ascending
+/// order. Pairs are found with a hash-based two-sum, triples by sorting and closing in with two
+/// pointers, and anything larger by meeting in the middle over hashed sums of half the numbers.
+pub fn k_sum<T: Summable>(nums: &[T], k: usize, target: T) -> Option<Vec<T>> {
+    if k > nums.len() {
+        return None;
+    }
+
+    let mut found = match k {
+        0 if target == T::default() => Some(vec![]),
+        0 => None,
+        1 => nums.iter().cloned().find(|&n| n == target).map(|n| vec![n]),
+        2 => two_sum(nums, target).map(|(a, b)| vec![a, b]),
+        3 => {
+            let mut sorted = nums.to_vec();
+            sorted.sort_unstable();
+            three_sum(&sorted, target)
+        }
+        _ => meet_in_the_middle(nums, k, target),
+    }?;
+    found.sort_unstable();
+
+    Some(found)
+}
+
+/// Every distinct tuple of `k` of the numbers that sums to `target`, each in ascending order and
+/// using a value no more often than it appears. The tuples are in ascending lexicographic order.
+pub fn k_sum_all<T: Summable>(nums: &[T], k: usize, target: T) -> Vec<Vec<T>> {
+    if k > nums.len() {
+        return Vec::new();
+    }
+
+    let mut sorted = nums.to_vec();
+    sorted.sort_unstable();
+
+    let mut found = Vec::new();
+    collect_k_sums(&sorted, k, target, &mut Vec::with_capacity(k), &mut found);
+
+    found
+}
+
+/// Extend `prefix` with each distinct choice of next number, closing in on the last two with
+/// two pointers. Skipping a number equal to the one before it at the same depth keeps repeated
+/// values from producing the same tuple twice.
+fn collect_k_sums<T: Summable>(
+    sorted: &[T],
+    k: usize,
+    target: T,
+    prefix: &mut Vec<T>,
+    found: &mut Vec<Vec<T>>,
+) {
+    match k {
+        0 => {
+            if target == T::default() {
+                found.push(prefix.clone());
+            }
+        }
+        1 => {
+            if sorted.binary_search(&target).is_ok() {
+                found.push(prefix.iter().cloned().chain(Some(target)).collect());
+            }
+        }
+        2 => {
+            let (mut low, mut high) = (0, sorted.len());
+            while low + 1 < high {
+                let (a, b) = (sorted[low], sorted[high - 1]);
+                match (a + b).cmp(&target) {
+                    Ordering::Equal => {
+                        found.push(prefix.iter().cloned().chain(vec![a, b]).collect());
+                        while low < high && sorted[low] == a {
+                            low += 1;
+                        }
+                        while high > low && sorted[high - 1] == b {
+                            high -= 1;
+                        }
+                    }
+                    Ordering::Less => low += 1,
+                    Ordering::Greater => high -= 1,
+                }
+            }
+        }
+        _ => {
+            for (i, &n) in sorted.iter().enumerate() {
+                if i > 0 && sorted[i - 1] == n {
+                    continue;
+                }
+                prefix.push(n);
+                collect_k_sums(&sorted[i + 1..], k - 1, target - n, prefix, found);
+                prefix.pop();
+            }
+        }
+    }
+}
+
+fn two_sum<T: Summable>(nums: &[T], target: T) -> Option<(T, T)> {
+    let mut seen = HashSet::with_capacity(nums.len());
+    for &n in nums {
+        if seen.contains(&(target - n)) {
+            return Some((target - n, n));
+        }
+        seen.insert(n);
+    }
+
+    None
+}
+
+fn three_sum<T: Summable>(sorted: &[T], target: T) -> Option<Vec<T>> {
+    for (i, &first) in sorted.iter().enumerate() {
+        let rest = &sorted[i + 1..];
+        let remaining = target - first;
+        let (mut low, mut high) = (0, rest.len());
+
+        while low + 1 < high {
+            match (rest[low] + rest[high - 1]).cmp(&remaining) {
+                Ordering::Equal => return Some(vec![first, rest[low], rest[high - 1]]),
+                Ordering::Less => low += 1,
+                Ordering::Greater => high -= 1,
+            }
+        }
+    }
+
+    None
+}
+
+fn sum_of<T: Summable>(nums: &[T], indices: &[usize]) -> T {
+    indices.iter().fold(T::default(), |acc, &i| acc + nums[i])
+}
+
+/// Every combination of `k` indices splits uniquely into its `k / 2` smallest and the rest, so
+/// hash the sums of the smaller halves and look up the complement of each larger half
+fn meet_in_the_middle<T: Summable>(nums: &[T], k: usize, target: T) -> Option<Vec<T>> {
+    let lower_size = k / 2;
+
+    let mut lower_halves: HashMap<T, Vec<Vec<usize>>> = HashMap::new();
+    for lower in (0..nums.len()).combinations(lower_size) {
+        lower_halves
+            .entry(sum_of(nums, &lower))
+            .or_default()
+            .push(lower);
+    }
+
+    (0..nums.len())
+        .combinations(k - lower_size)
+        .find_map(|upper| {
+            lower_halves
+                .get(&(target - sum_of(nums, &upper)))?
+                .iter()
+                .find(|lower| lower.last() < upper.first())
+                .map(|lower| lower.iter().chain(&upper).map(|&i| nums[i]).collect())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENTRIES: [i32; 6] = [1721, 979, 366, 299, 675, 1456];
+
+    #[test]
+    fn solve_for_each_k() {
+        assert_eq!(k_sum(&ENTRIES, 1, 366), Some(vec![366]));
+        assert_eq!(k_sum(&ENTRIES, 2, 2020), Some(vec![299, 1721]));
+        assert_eq!(k_sum(&ENTRIES, 3, 2020), Some(vec![366, 675, 979]));
+        assert_eq!(k_sum(&ENTRIES, 4, 3365), Some(vec![299, 366, 979, 1721]));
+        assert_eq!(k_sum(&ENTRIES, 2, 3442), None);
+        assert_eq!(k_sum(&ENTRIES, 7, 5496), None);
+    }
+
+    #[test]
+    fn more_numbers_than_given() {
+        assert_eq!(k_sum(&ENTRIES, 4_000_000_000, 2020), None);
+        assert_eq!(k_sum(&ENTRIES, usize::MAX, 2020), None);
+        assert!(k_sum_all(&ENTRIES, usize::MAX, 2020).is_empty());
+    }
+
+    #[test]
+    fn enumerate_distinct_tuples() {
+        let nums = [5, 1, 3, 3, 2, 4, 3];
+
+        assert_eq!(
+            k_sum_all(&nums, 2, 6),
+            vec![vec![1, 5], vec![2, 4], vec![3, 3]]
+        );
+        assert_eq!(
+            k_sum_all(&nums, 3, 9),
+            vec![vec![1, 3, 5], vec![2, 3, 4], vec![3, 3, 3]]
+        );
+        assert_eq!(k_sum_all(&[3, 5], 2, 6), Vec::<Vec<i32>>::new());
+    }
+
+    #[test]
+    fn agree_with_brute_force() {
+        let nums = [3, -4, 7, 7, 0, 12, -9, 5];
+
+        for k in 0..=nums.len() {
+            for target in -20..=40 {
+                let expected = nums
+                    .iter()
+                    .combinations(k)
+                    .any(|c| c.into_iter().sum::<i32>() == target);
+
+                let mut all: Vec<Vec<i32>> = nums
+                    .iter()
+                    .cloned()
+                    .combinations(k)
+                    .map(|c| c.into_iter().sorted().collect())
+                    .filter(|c: &Vec<i32>| c.iter().sum::<i32>() == target)
+                    .collect();
+                all.sort();
+                all.dedup();
+                assert_eq!(k_sum_all(&nums, k, target), all);
+
+                match k_sum(&nums, k, target) {
+                    Some(found) => {
+                        assert_eq!(found.len(), k);
+                        assert_eq!(found.iter().sum::<i32>(), target);
+                    }
+                    None => assert!(!expected, "missed k = {}, target = {}", k, target),
+                }
+            }
+        }
+    }
+}
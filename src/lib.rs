@@ -0,0 +1,45 @@
+//! Solutions to Advent of Code puzzles and the tooling used to run, check and benchmark them.
+//!
+//! Each day implements [`Challenge`], built from its puzzle input by [`challenge_from_day`]:
+//!
+//! ```
+//! use aoc2020::{challenge_from_day, Answer};
+//!
+//! let mut day = challenge_from_day(2020, 1, "1721\n979\n366\n299\n675\n1456").unwrap();
+//! day.setup().unwrap();
+//! assert_eq!(day.part_1().unwrap(), Answer::Integer(514579));
+//! ```
+//!
+//! The parsers and solvers behind each day are exposed from the year's module tree, such as
+//! [`y2020`].
+
+#[macro_use]
+extern crate lazy_static;
+
+pub mod alloc;
+pub mod baseline;
+pub mod bench;
+pub mod check;
+pub mod config;
+#[macro_use]
+pub mod days;
+pub mod error;
+pub mod fetch;
+pub mod input;
+pub mod ksum;
+pub mod output;
+pub mod runner;
+pub mod scaffold;
+pub mod submit;
+pub mod toml_file;
+pub mod watch;
+
+pub use days::{
+    challenge_from_day, configured_challenge, implemented_days, implemented_years, registration,
+    Answer, Challenge, Params, DEFAULT_YEAR,
+};
+pub use error::{Error, Result};
+
+register_years! {
+    y2020,
+}
@@ -1,87 +1,545 @@
-#[macro_use] extern crate lazy_static;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::exit;
-use std::time::SystemTime;
 
-use clap::{App, Arg};
-use days::challenge_from_day;
+use aoc2020::baseline::{Baseline, BaselineOptions};
+use aoc2020::bench::{bench_days, BenchOptions};
+use aoc2020::check::ExpectedAnswers;
+use aoc2020::config::{Config, DEFAULT_CONFIG};
+use aoc2020::fetch::{session_token, Fetcher, InputCache, DEFAULT_BASE_URL};
+use aoc2020::input::read_input;
+use aoc2020::output::{report, Format};
+use aoc2020::runner::{parse_day, run_day, run_days, Parts};
+use aoc2020::scaffold::new_day;
+use aoc2020::submit::{now, submit_part, Submission, Verdict};
+use aoc2020::toml_file::TomlFile;
+use aoc2020::watch::{watch_day, DayWatch};
+use aoc2020::{
+    implemented_days, implemented_years, registration, Error, Params, Result, DEFAULT_YEAR,
+};
+use clap::{App, AppSettings, Arg, ArgMatches};
 
-mod days;
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static ALLOCATOR: aoc2020::alloc::CountingAllocator = aoc2020::alloc::CountingAllocator;
 
 fn main() {
-    let matches = App::new("Advent of Code 2020")
+    let matches = App::new("Advent of Code")
         .version("0.1.0")
         .author("David Richardson <david@tankski.co.uk>")
-        .about("A simple CLI for building and running my AoC 2020 solutions")
+        .about("A simple CLI for building and running my AoC solutions")
+        .setting(AppSettings::SubcommandsNegateReqs)
+        .subcommand(
+            App::new("new-day")
+                .about("Generate src/yYYYY/day_N.rs from a template and register it")
+                .arg(
+                    Arg::new("day")
+                        .value_name("DAY")
+                        .about("The day to create")
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("title")
+                        .short('t')
+                        .long("title")
+                        .value_name("TITLE")
+                        .about("The puzzle's title")
+                        .default_value("Untitled")
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            App::new("submit")
+                .about("Solve a part and submit its answer, recording the attempt")
+                .arg(
+                    Arg::new("day")
+                        .short('d')
+                        .long("day")
+                        .value_name("DAY")
+                        .about("The day to submit")
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("part")
+                        .short('p')
+                        .long("part")
+                        .value_name("PART")
+                        .about("The part to submit")
+                        .possible_values(&["1", "2"])
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("log")
+                        .long("log")
+                        .value_name("FILE")
+                        .about("The log of submitted answers")
+                        .default_value("attempts.toml")
+                        .takes_value(true),
+                ),
+        )
+        .arg(
+            Arg::new("year")
+                .short('y')
+                .long("year")
+                .value_name("YEAR")
+                .about("The event year, 2020 by default")
+                .global(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new("config")
+                .long("config")
+                .value_name("FILE")
+                .about("The project config file, aoc.toml by default")
+                .takes_value(true),
+        )
         .arg(
             Arg::new("day")
                 .short('d')
                 .long("day")
                 .value_name("DAY")
                 .about("The day of the challenge to run")
-                .required(true),
+                .required_unless_present_any(["all", "list"]),
         )
         .arg(
             Arg::new("input")
                 .short('i')
                 .long("input")
                 .value_name("FILE")
-                .about("The challenge input file")
-                .required(true)
+                .about("The input file, or - to read stdin. Defaults to the cached input")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new("all")
+                .short('a')
+                .long("all")
+                .about("Run every implemented day")
+                .conflicts_with_all(&["day", "input"]),
+        )
+        .arg(
+            Arg::new("param")
+                .long("param")
+                .value_name("KEY=VALUE")
+                .about("Set a parameter of the day, such as Day 1's target; may be repeated")
+                .multiple_occurrences(true)
+                .conflicts_with("all")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new("target")
+                .long("target")
+                .value_name("N")
+                .about("Shorthand for --param target=N")
+                .conflicts_with("all")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new("k")
+                .long("k")
+                .value_name("K")
+                .about("Shorthand for --param k=K")
+                .conflicts_with("all")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new("policy")
+                .long("policy")
+                .value_name("POLICIES")
+                .about("Shorthand for --param policy=POLICIES, Day 2's rules separated by ;")
+                .conflicts_with("all")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new("lenient")
+                .long("lenient")
+                .about("Shorthand for --param lenient=true, skipping and reporting malformed lines")
+                .conflicts_with("all"),
+        )
+        .arg(
+            Arg::new("sequential")
+                .long("sequential")
+                .about("Run the days one at a time instead of in parallel, for steadier timings")
+                .requires("all"),
+        )
+        .arg(
+            Arg::new("list")
+                .short('l')
+                .long("list")
+                .about("List the implemented days")
+                .conflicts_with_all(&["day", "input", "all"]),
+        )
+        .arg(
+            Arg::new("part")
+                .short('p')
+                .long("part")
+                .value_name("PART")
+                .about("Which parts of the challenge to solve")
+                .possible_values(&["1", "2", "both"])
+                .default_value("both")
+                .conflicts_with("bench")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new("parse-only")
+                .long("parse-only")
+                .about("Only run setup, and print the parsed input")
+                .conflicts_with_all(&["all", "bench", "check", "format"]),
+        )
+        .arg(
+            Arg::new("watch")
+                .short('w')
+                .long("watch")
+                .about("Re-run the day whenever its input or expected answers change")
+                .conflicts_with_all(&["all", "bench", "parse-only", "format"]),
+        )
+        .arg(
+            Arg::new("inputs")
+                .long("inputs")
+                .value_name("DIR")
+                .about("The directory caching YEAR/day_N.txt input files, inputs by default")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new("base-url")
+                .long("base-url")
+                .value_name("URL")
+                .about("The server to download missing inputs from")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new("session-file")
+                .long("session-file")
+                .value_name("FILE")
+                .about("The session token file if AOC_SESSION is unset, .aoc_session by default")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new("check")
+                .short('c')
+                .long("check")
+                .value_name("FILE")
+                .about("A TOML file of expected answers to verify the results against")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new("bench")
+                .short('b')
+                .long("bench")
+                .value_name("N")
+                .about("Benchmark the challenge over N iterations instead of running it once")
+                .min_values(0)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new("warmup")
+                .long("warmup")
+                .value_name("N")
+                .about("The number of untimed iterations to run before benchmarking, 3 by default")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new("save-baseline")
+                .long("save-baseline")
+                .value_name("FILE")
+                .about("Record the benchmark medians in a baseline file")
+                .requires("bench")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new("compare-baseline")
+                .long("compare-baseline")
+                .value_name("FILE")
+                .about("Fail if any benchmark median regressed against a baseline file")
+                .requires("bench")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new("threshold")
+                .long("threshold")
+                .value_name("PERCENT")
+                .about("The slowdown allowed before a phase counts as a regression")
+                .default_value("10")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new("format")
+                .short('f')
+                .long("format")
+                .value_name("FORMAT")
+                .about("How to print run results; json emits one object per line for --all")
+                .possible_values(&["text", "json"])
+                .conflicts_with("bench")
                 .takes_value(true),
         )
         .get_matches();
 
-    let day: u32 = matches.value_of_t_or_exit("day");
-    let input_path = match matches
-        .value_of("input")
-        .and_then(|path_str| Some(Path::new(path_str)))
-    {
-        Some(p) => p,
-        None => Path::new(""),
+    let config = match matches.value_of("config") {
+        Some(path) => Config::load(Path::new(path)),
+        None => Config::load_or_default(Path::new(DEFAULT_CONFIG)),
+    }
+    .unwrap_or_else(|err| {
+        eprintln!("Failed to load the config: {}", err);
+        exit(1);
+    });
+
+    let year_of = |matches: &ArgMatches| -> u32 {
+        match matches.value_of("year") {
+            Some(_) => matches.value_of_t_or_exit("year"),
+            None => config.year.unwrap_or(DEFAULT_YEAR),
+        }
     };
+    let year = year_of(&matches);
 
-    if input_path == Path::new("") {
-        eprintln!("Please provide an input file for the challenge");
-        exit(1);
+    if let Some(matches) = matches.subcommand_matches("new-day") {
+        let day: u32 = matches.value_of_t_or_exit("day");
+        let title = matches.value_of("title").unwrap_or("Untitled");
+
+        match new_day(Path::new("."), year_of(matches), day, title) {
+            Ok(path) => println!("Created {}", path.display()),
+            Err(err) => {
+                eprintln!("Failed to create day {}: {}", day, err);
+                exit(1);
+            }
+        }
+        return;
     }
 
-    if !input_path.exists() {
-        eprintln!("Input file \"{:?}\" does not exist", input_path);
+    if matches.is_present("list") {
+        list_days(year);
+        return;
     }
 
-    let exit_code: i32 = challenge_from_day(&day, input_path).map_or_else(
-        |err| {
-            eprintln!("Failed to run day {}: {}", &day, err);
-            -1
-        },
-        |mut c| {
-            println!("Starting day {}\n", &day);
+    let format: Format = match matches.value_of("format") {
+        Some(_) => matches.value_of_t_or_exit("format"),
+        None => config.format.unwrap_or(Format::Text),
+    };
+    let parts: Parts = matches.value_of_t_or_exit("part");
+    let mut params = Params::new();
+    for pair in matches.values_of("param").into_iter().flatten() {
+        if let Err(err) = params.insert_pair(pair) {
+            eprintln!("{}", err);
+            exit(1);
+        }
+    }
+    for key in &["target", "k", "policy"] {
+        if let Some(value) = matches.value_of(key) {
+            params.insert(*key, value);
+        }
+    }
+    if matches.is_present("lenient") {
+        params.insert("lenient", "true");
+    }
+    let bench = if matches.is_present("bench") {
+        Some(BenchOptions {
+            iterations: match matches.value_of("bench") {
+                Some(_) => matches.value_of_t_or_exit("bench"),
+                None => config.bench_iterations.unwrap_or(100),
+            },
+            warmup: match matches.value_of("warmup") {
+                Some(_) => matches.value_of_t_or_exit("warmup"),
+                None => config.bench_warmup.unwrap_or(3),
+            },
+        })
+    } else {
+        None
+    };
+    let baselines = BaselineOptions {
+        save: matches.value_of("save-baseline").map(PathBuf::from),
+        compare: matches.value_of("compare-baseline").map(|path| {
+            Baseline::load(Path::new(path)).unwrap_or_else(|err| {
+                eprintln!("Failed to load baseline from {}: {}", path, err);
+                exit(1);
+            })
+        }),
+        threshold: matches.value_of_t_or_exit("threshold"),
+    };
 
-            let start_time = SystemTime::now();
-            c.setup();
-            let setup_time = SystemTime::now();
+    let check_path = matches
+        .value_of("check")
+        .map(PathBuf::from)
+        .or_else(|| config.check.clone());
+    let expected = check_path
+        .as_ref()
+        .filter(|_| !matches.is_present("parse-only"))
+        .map(|path| {
+            ExpectedAnswers::load(path).unwrap_or_else(|err| {
+                eprintln!("Failed to load expected answers from {:?}: {}", path, err);
+                exit(1);
+            })
+        });
 
-            c.part_1();
-            let part_1_time = SystemTime::now();
+    let fetcher = Fetcher::new(
+        matches
+            .value_of("base-url")
+            .or(config.base_url.as_deref())
+            .unwrap_or(DEFAULT_BASE_URL),
+        session_token(
+            &matches
+                .value_of("session-file")
+                .map(PathBuf::from)
+                .or_else(|| config.session_file.clone())
+                .unwrap_or_else(|| PathBuf::from(".aoc_session")),
+        ),
+    );
+    let cache = match (matches.value_of("inputs"), &config.inputs) {
+        (Some(dir), _) => InputCache::new(Path::new(dir), fetcher),
+        (None, Some(pattern)) => InputCache::with_pattern(pattern, fetcher),
+        (None, None) => InputCache::new(Path::new("inputs"), fetcher),
+    };
 
-            c.part_2();
-            let part_2_time = SystemTime::now();
+    if let Some(matches) = matches.subcommand_matches("submit") {
+        let day: u32 = matches.value_of_t_or_exit("day");
+        let part: u8 = matches.value_of_t_or_exit("part");
+        let log_path = Path::new(matches.value_of("log").unwrap_or("attempts.toml"));
 
-            println!("Answers for day {}:", day);
-            println!("{}", c.format_answers());
+        match submit_part(year_of(matches), day, part, log_path, &cache, now()) {
+            Ok(Submission::Refused { answer, reason }) => {
+                eprintln!("Not submitting {}: {}", answer, reason);
+                exit(1);
+            }
+            Ok(Submission::Submitted { answer, verdict }) => {
+                println!("Day {} part {}: {} is {}", day, part, answer, verdict);
+                exit(if verdict == Verdict::Correct { 0 } else { 1 });
+            }
+            Err(err) => {
+                eprintln!("Failed to submit day {} part {}: {}", day, part, err);
+                exit(1);
+            }
+        }
+    }
 
-            println!(
-                "\nTime beakdowns:\nSetup: {:?}\nPart 1: {:?}\nPart 2: {:?}\nTotal: {:?}",
-                setup_time.duration_since(start_time).unwrap(),
-                part_1_time.duration_since(setup_time).unwrap(),
-                part_2_time.duration_since(part_1_time).unwrap(),
-                part_2_time.duration_since(start_time).unwrap()
+    if matches.is_present("all") {
+        let days = implemented_days(year);
+        if days.is_empty() {
+            let years: Vec<String> = implemented_years().iter().map(u32::to_string).collect();
+            eprintln!(
+                "No days of {} are implemented (years: {})",
+                year,
+                years.join(", ")
             );
+            exit(1);
+        }
+
+        let inputs: Vec<(u32, Result<String>)> = days
+            .into_iter()
+            .map(|day| (day, cache.get(year, day)))
+            .collect();
 
+        if let Some(options) = bench {
+            exit(if bench_days(year, inputs, &params, options, &baselines) {
+                0
+            } else {
+                1
+            });
+        }
+
+        let results = run_days(
+            year,
+            inputs,
+            &params,
+            parts,
+            matches.is_present("sequential"),
+        );
+
+        exit(if report(year, &results, expected.as_ref(), format, true) {
+            0
+        } else {
+            1
+        });
+    }
+
+    let day: u32 = matches.value_of_t_or_exit("day");
+    // Check the day can run before reading, or downloading, its input
+    if registration(year, day).is_none() {
+        eprintln!(
+            "Failed to run day {}: {}",
+            day,
+            Error::NotImplemented(year, day)
+        );
+        exit(1);
+    }
+
+    if matches.is_present("watch") {
+        let input_path = match matches.value_of("input") {
+            Some("-") => {
+                eprintln!("Can't watch stdin for changes");
+                exit(1);
+            }
+            Some(path) => PathBuf::from(path),
+            None => {
+                if let Err(err) = cache.get(year, day) {
+                    eprintln!("Failed to read the input for day {}: {}", day, err);
+                    exit(1);
+                }
+                cache.path(year, day)
+            }
+        };
+
+        watch_day(DayWatch::new(
+            year, day, input_path, check_path, &params, parts,
+        ));
+    }
+
+    let input = match matches.value_of("input").map(Path::new) {
+        Some(path) if path != Path::new("-") && !path.exists() => {
+            eprintln!("Input file \"{:?}\" does not exist", path);
+            exit(1);
+        }
+        Some(path) => read_input(path),
+        None => cache.get(year, day),
+    }
+    .unwrap_or_else(|err| {
+        eprintln!("Failed to read the input for day {}: {}", day, err);
+        exit(1);
+    });
+
+    if let Some(options) = bench {
+        let inputs = vec![(day, Ok(input))];
+        exit(if bench_days(year, inputs, &params, options, &baselines) {
+            0
+        } else {
+            1
+        });
+    }
+
+    if matches.is_present("parse-only") {
+        match parse_day(year, day, &input, &params) {
+            Ok(parsed) => println!("{}", parsed),
+            Err(err) => {
+                eprintln!("Failed to parse the input for day {}: {}", day, err);
+                exit(1);
+            }
+        }
+        return;
+    }
+
+    if format == Format::Text {
+        println!("Starting day {}\n", &day);
+    }
+
+    let results = vec![(day, run_day(year, day, &input, &params, parts))];
+    exit(
+        if report(year, &results, expected.as_ref(), format, false) {
             0
+        } else {
+            1
         },
     );
+}
+
+fn list_days(year: u32) {
+    let years: Vec<String> = implemented_years().iter().map(u32::to_string).collect();
+    println!("Advent of Code {} (years: {})\n", year, years.join(", "));
+
+    let mut missing = Vec::new();
 
-    exit(exit_code);
+    for day in 1..=25 {
+        match registration(year, day) {
+            Some(r) => println!("Day {:>2}: {}", day, r.title),
+            None => missing.push(day.to_string()),
+        }
+    }
+
+    if !missing.is_empty() {
+        println!("\nNot implemented: {}", missing.join(", "));
+    }
 }
@@ -0,0 +1,327 @@
+use std::convert::TryFrom;
+use std::str::FromStr;
+use std::time::Duration;
+
+use serde_json::{json, Map, Value};
+
+use crate::alloc::MemoryStats;
+use crate::check::{print_checks, ExpectedAnswers, Outcome, PartCheck};
+use crate::days::Answer;
+use crate::error::Result;
+use crate::runner::{print_result, print_summary, RunResult};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("Unsupported output format \"{}\"", s)),
+        }
+    }
+}
+
+/// Integers that don't fit in an `i64` are emitted as strings so JSON consumers don't lose
+/// precision
+pub fn answer_json(answer: &Answer) -> Value {
+    match answer {
+        Answer::Integer(v) => json!(v),
+        Answer::BigInteger(v) => {
+            i64::try_from(*v).map_or_else(|_| json!(v.to_string()), |v| json!(v))
+        }
+        Answer::Text(s) => json!(s),
+        Answer::List(items) => Value::Array(items.iter().map(answer_json).collect()),
+    }
+}
+
+fn nanos(d: Duration) -> Value {
+    json!(d.as_nanos() as u64)
+}
+
+fn memory_json(stats: &MemoryStats) -> Value {
+    json!({
+        "peak_bytes": stats.peak,
+        "total_bytes": stats.total,
+        "allocations": stats.allocations,
+    })
+}
+
+fn check_json(check: &PartCheck) -> Value {
+    match &check.outcome {
+        Outcome::Pass => json!({ "status": "pass" }),
+        Outcome::Unknown => json!({ "status": "unknown" }),
+        Outcome::Fail { expected, actual } => json!({
+            "status": "fail",
+            "expected": expected,
+            "actual": actual,
+        }),
+    }
+}
+
+/// A JSON object describing a day's answers, phase timings in nanoseconds and, when they were
+/// counted, phase allocations, or the error that stopped it
+pub fn result_json(
+    year: u32,
+    day: u32,
+    result: &Result<RunResult>,
+    checks: Option<&[PartCheck]>,
+) -> Value {
+    let mut obj = Map::new();
+    obj.insert("year".to_string(), json!(year));
+    obj.insert("day".to_string(), json!(day));
+
+    match result {
+        Ok(r) => {
+            obj.insert(
+                "answers".to_string(),
+                json!({
+                    "part_1": r.part_1.as_ref().map_or(Value::Null, answer_json),
+                    "part_2": r.part_2.as_ref().map_or(Value::Null, answer_json),
+                }),
+            );
+            obj.insert(
+                "timings_ns".to_string(),
+                json!({
+                    "setup": nanos(r.timings.setup),
+                    "part_1": nanos(r.timings.part_1),
+                    "part_2": nanos(r.timings.part_2),
+                    "total": nanos(r.timings.total()),
+                }),
+            );
+            if !r.warnings.is_empty() {
+                obj.insert("warnings".to_string(), json!(r.warnings));
+            }
+            if let Some(memory) = &r.memory {
+                obj.insert(
+                    "memory".to_string(),
+                    json!({
+                        "setup": memory_json(&memory.setup),
+                        "part_1": memory_json(&memory.part_1),
+                        "part_2": memory_json(&memory.part_2),
+                    }),
+                );
+            }
+            obj.insert("error".to_string(), Value::Null);
+        }
+        Err(err) => {
+            obj.insert("error".to_string(), json!(err.to_string()));
+        }
+    }
+
+    if let Some(checks) = checks {
+        obj.insert(
+            "checks".to_string(),
+            checks
+                .iter()
+                .map(|c| (format!("part_{}", c.part), check_json(c)))
+                .collect::<Map<String, Value>>()
+                .into(),
+        );
+    }
+
+    Value::Object(obj)
+}
+
+/// Print the results, and any checks against the expected answers, in the requested format.
+/// `summary` prints text results as a table and JSON ones compactly, a line per day. Returns
+/// whether every day ran and passed its checks.
+pub fn report(
+    year: u32,
+    results: &[(u32, Result<RunResult>)],
+    expected: Option<&ExpectedAnswers>,
+    format: Format,
+    summary: bool,
+) -> bool {
+    let checks: Vec<Vec<PartCheck>> = results
+        .iter()
+        .map(|(_, r)| match (expected, r) {
+            (Some(expected), Ok(r)) => expected.check(r),
+            _ => Vec::new(),
+        })
+        .collect();
+
+    match format {
+        Format::Json => {
+            for ((day, result), checks) in results.iter().zip(checks.iter()) {
+                let value = result_json(year, *day, result, expected.map(|_| checks.as_slice()));
+                if summary {
+                    println!("{}", value);
+                } else {
+                    println!("{:#}", value);
+                }
+            }
+        }
+        Format::Text => {
+            if summary {
+                print_summary(results);
+            } else {
+                for (day, result) in results {
+                    match result {
+                        Ok(r) => print_result(r),
+                        Err(err) => eprintln!("Failed to run day {}: {}", day, err),
+                    }
+                }
+            }
+
+            if expected.is_some() {
+                println!();
+                print_checks(&checks.concat());
+            }
+        }
+    }
+
+    let failed = results.iter().any(|(_, r)| r.is_err())
+        || checks
+            .iter()
+            .flatten()
+            .any(|c| matches!(c.outcome, Outcome::Fail { .. }));
+
+    !failed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+    use crate::runner::Memory;
+    use crate::toml_file::TomlFile;
+
+    fn run(part_1: Answer, part_2: Option<Answer>) -> RunResult {
+        RunResult::with_answers(2020, 1, Some(part_1), part_2, [300, 20, 1])
+    }
+
+    #[test]
+    fn answers_as_json() {
+        assert_eq!(answer_json(&Answer::from(-7)), json!(-7));
+        assert_eq!(answer_json(&Answer::from(42i128)), json!(42));
+        assert_eq!(
+            answer_json(&Answer::from(i128::from(i64::MAX) + 1)),
+            json!("9223372036854775808")
+        );
+        assert_eq!(
+            answer_json(&Answer::List(vec![
+                Answer::from("abc"),
+                Answer::from(vec![1, 2]),
+            ])),
+            json!(["abc", [1, 2]])
+        );
+    }
+
+    #[test]
+    fn successful_run() {
+        let mut result = run(
+            Answer::from(-40_000_000_000_000_000_000i128),
+            Some(Answer::from("x")),
+        );
+        result.warnings.push("Skipped line 2".to_string());
+        result.memory = Some(Memory {
+            setup: MemoryStats {
+                peak: 64,
+                total: 96,
+                allocations: 2,
+            },
+            ..Memory::default()
+        });
+
+        assert_eq!(
+            result_json(2020, 1, &Ok(result), None),
+            json!({
+                "year": 2020,
+                "day": 1,
+                "answers": { "part_1": "-40000000000000000000", "part_2": "x" },
+                "timings_ns": {
+                    "setup": 300_000,
+                    "part_1": 20_000,
+                    "part_2": 1_000,
+                    "total": 321_000,
+                },
+                "warnings": ["Skipped line 2"],
+                "memory": {
+                    "setup": { "peak_bytes": 64, "total_bytes": 96, "allocations": 2 },
+                    "part_1": { "peak_bytes": 0, "total_bytes": 0, "allocations": 0 },
+                    "part_2": { "peak_bytes": 0, "total_bytes": 0, "allocations": 0 },
+                },
+                "error": null,
+            })
+        );
+    }
+
+    #[test]
+    fn failed_run() {
+        assert_eq!(
+            result_json(2019, 3, &Err(Error::no_solution("nothing")), None),
+            json!({
+                "year": 2019,
+                "day": 3,
+                "error": "No solution found: nothing",
+            })
+        );
+    }
+
+    #[test]
+    fn checked_run() {
+        let checks = [
+            PartCheck {
+                day: 1,
+                part: 1,
+                outcome: Outcome::Pass,
+            },
+            PartCheck {
+                day: 1,
+                part: 2,
+                outcome: Outcome::Fail {
+                    expected: "5".to_string(),
+                    actual: "6".to_string(),
+                },
+            },
+        ];
+        let json = result_json(
+            2020,
+            1,
+            &Ok(run(Answer::from(4), Some(Answer::from(6)))),
+            Some(&checks),
+        );
+
+        assert_eq!(json["answers"], json!({ "part_1": 4, "part_2": 6 }));
+        assert_eq!(json.get("warnings"), None);
+        assert_eq!(json.get("memory"), None);
+        assert_eq!(
+            json["checks"],
+            json!({
+                "part_1": { "status": "pass" },
+                "part_2": { "status": "fail", "expected": "5", "actual": "6" },
+            })
+        );
+        assert_eq!(
+            result_json(2020, 1, &Ok(run(Answer::from(4), None)), Some(&[]))["answers"],
+            json!({ "part_1": 4, "part_2": null })
+        );
+    }
+
+    #[test]
+    fn report_failures() {
+        let expected = ExpectedAnswers::parse("[day_1]\npart_1 = 4").unwrap();
+        let passing = vec![(1, Ok(run(Answer::from(4), None)))];
+        let wrong = vec![(1, Ok(run(Answer::from(5), None)))];
+        let failed = vec![
+            (1, Ok(run(Answer::from(4), None))),
+            (2, Err(Error::no_solution("nothing"))),
+        ];
+
+        for &format in &[Format::Text, Format::Json] {
+            for &summary in &[false, true] {
+                assert!(report(2020, &passing, Some(&expected), format, summary));
+                assert!(report(2020, &wrong, None, format, summary));
+                assert!(!report(2020, &wrong, Some(&expected), format, summary));
+                assert!(!report(2020, &failed, None, format, summary));
+            }
+        }
+    }
+}
@@ -0,0 +1,495 @@
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use rayon::prelude::*;
+
+use crate::alloc::{self, format_bytes, MemoryStats};
+use crate::days::{configured_challenge, Answer, Params};
+use crate::error::Result;
+
+/// Which parts of a challenge to solve after `setup`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Parts {
+    One,
+    Two,
+    Both,
+}
+
+impl Parts {
+    pub fn includes(self, part: u8) -> bool {
+        matches!(
+            (self, part),
+            (Parts::Both, _) | (Parts::One, 1) | (Parts::Two, 2)
+        )
+    }
+}
+
+impl FromStr for Parts {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Parts::One),
+            "2" => Ok(Parts::Two),
+            "both" => Ok(Parts::Both),
+            _ => Err(format!("Unsupported part \"{}\"", s)),
+        }
+    }
+}
+
+/// How long each phase took, with zero for any part that wasn't run
+#[derive(Debug, Clone, Copy)]
+pub struct Timings {
+    pub setup: Duration,
+    pub part_1: Duration,
+    pub part_2: Duration,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.setup + self.part_1 + self.part_2
+    }
+}
+
+/// How much each phase allocated, with nothing for any part that wasn't run
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Memory {
+    pub setup: MemoryStats,
+    pub part_1: MemoryStats,
+    pub part_2: MemoryStats,
+}
+
+impl Memory {
+    /// The highest peak of any phase
+    pub fn peak(&self) -> usize {
+        self.setup.peak.max(self.part_1.peak).max(self.part_2.peak)
+    }
+
+    /// Every byte allocated across the phases
+    pub fn total(&self) -> usize {
+        self.setup.total + self.part_1.total + self.part_2.total
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RunResult {
+    pub year: u32,
+    pub day: u32,
+    /// The answer to each part, or `None` if it wasn't run
+    pub part_1: Option<Answer>,
+    pub part_2: Option<Answer>,
+    pub timings: Timings,
+    /// The allocations made by each phase, if the counting allocator is installed
+    pub memory: Option<Memory>,
+    /// Problems `setup` worked around, such as skipped input lines
+    pub warnings: Vec<String>,
+}
+
+#[cfg(test)]
+impl RunResult {
+    /// A result with the given answers and setup, part 1 and part 2 times in microseconds, and
+    /// no memory or warnings
+    pub(crate) fn with_answers(
+        year: u32,
+        day: u32,
+        part_1: Option<Answer>,
+        part_2: Option<Answer>,
+        micros: [u64; 3],
+    ) -> RunResult {
+        RunResult {
+            year,
+            day,
+            part_1,
+            part_2,
+            timings: Timings {
+                setup: Duration::from_micros(micros[0]),
+                part_1: Duration::from_micros(micros[1]),
+                part_2: Duration::from_micros(micros[2]),
+            },
+            memory: None,
+            warnings: Vec::new(),
+        }
+    }
+}
+
+pub fn run_day(
+    year: u32,
+    day: u32,
+    input: &str,
+    params: &Params,
+    parts: Parts,
+) -> Result<RunResult> {
+    let mut c = configured_challenge(year, day, input, params)?;
+
+    let mut memory = Memory::default();
+
+    alloc::start_phase();
+    let start_time = Instant::now();
+    c.setup()?;
+    let setup = start_time.elapsed();
+    memory.setup = alloc::end_phase();
+
+    let (part_1, part_1_time) = if parts.includes(1) {
+        alloc::start_phase();
+        let start_time = Instant::now();
+        let answer = c.part_1()?;
+        let elapsed = start_time.elapsed();
+        memory.part_1 = alloc::end_phase();
+        (Some(answer), elapsed)
+    } else {
+        (None, Duration::default())
+    };
+
+    let (part_2, part_2_time) = if parts.includes(2) {
+        alloc::start_phase();
+        let start_time = Instant::now();
+        let answer = c.part_2()?;
+        let elapsed = start_time.elapsed();
+        memory.part_2 = alloc::end_phase();
+        (Some(answer), elapsed)
+    } else {
+        (None, Duration::default())
+    };
+
+    Ok(RunResult {
+        year,
+        day,
+        part_1,
+        part_2,
+        timings: Timings {
+            setup,
+            part_1: part_1_time,
+            part_2: part_2_time,
+        },
+        memory: if alloc::is_counting() {
+            Some(memory)
+        } else {
+            None
+        },
+        warnings: c.warnings(),
+    })
+}
+
+/// Run each day whose input could be read, concurrently on the rayon thread pool unless
+/// `sequential` is set. The results are returned in the same order as the inputs.
+pub fn run_days(
+    year: u32,
+    inputs: Vec<(u32, Result<String>)>,
+    params: &Params,
+    parts: Parts,
+    sequential: bool,
+) -> Vec<(u32, Result<RunResult>)> {
+    let run = |(day, input): (u32, Result<String>)| {
+        (
+            day,
+            input.and_then(|s| run_day(year, day, &s, params, parts)),
+        )
+    };
+
+    if sequential {
+        inputs.into_iter().map(run).collect()
+    } else {
+        inputs.into_par_iter().map(run).collect()
+    }
+}
+
+/// Run only a day's `setup`, returning the parsed input rendered with `Debug`
+pub fn parse_day(year: u32, day: u32, input: &str, params: &Params) -> Result<String> {
+    let mut c = configured_challenge(year, day, input, params)?;
+    c.setup()?;
+
+    Ok(format!("{:#?}", c.parsed()))
+}
+
+fn answer_or_skipped(answer: &Option<Answer>) -> String {
+    answer
+        .as_ref()
+        .map_or_else(|| "-".to_string(), Answer::to_string)
+}
+
+/// A part's answer for printing on its own. A list of text, like a report, gets a line per entry
+/// rather than being joined onto one line.
+fn answer_lines(part: u8, answer: &Option<Answer>) -> String {
+    match answer {
+        Some(Answer::List(items))
+            if !items.is_empty() && items.iter().all(|a| matches!(a, Answer::Text(_))) =>
+        {
+            items.iter().fold(format!("Part {}:", part), |acc, item| {
+                format!("{}\n  {}", acc, item)
+            })
+        }
+        _ => format!("Part {}: {}", part, answer_or_skipped(answer)),
+    }
+}
+
+pub fn print_result(result: &RunResult) {
+    for warning in &result.warnings {
+        println!("Warning: {}", warning);
+    }
+    if !result.warnings.is_empty() {
+        println!();
+    }
+
+    println!("Answers for day {}:", result.day);
+    println!("{}", answer_lines(1, &result.part_1));
+    println!("{}", answer_lines(2, &result.part_2));
+
+    println!(
+        "\nTime beakdowns:\nSetup: {:?}\nPart 1: {:?}\nPart 2: {:?}\nTotal: {:?}",
+        result.timings.setup,
+        result.timings.part_1,
+        result.timings.part_2,
+        result.timings.total()
+    );
+
+    if let Some(memory) = &result.memory {
+        println!(
+            "\nMemory breakdowns:\nSetup: {}\nPart 1: {}\nPart 2: {}",
+            memory_usage(&memory.setup),
+            memory_usage(&memory.part_1),
+            memory_usage(&memory.part_2)
+        );
+    }
+}
+
+fn memory_usage(stats: &MemoryStats) -> String {
+    format!(
+        "peak {}, total {} in {} allocations",
+        format_bytes(stats.peak),
+        format_bytes(stats.total),
+        stats.allocations
+    )
+}
+
+const DAY_COLUMN: usize = 4;
+const ANSWER_COLUMN: usize = 16;
+const TIME_COLUMN: usize = 12;
+const MEMORY_COLUMN: usize = 10;
+
+/// Right-align each cell to its column's width, separating the columns with `|`
+fn table_row(cells: &[String], widths: &[usize]) -> String {
+    cells
+        .iter()
+        .zip(widths)
+        .map(|(cell, &width)| format!("{:>1$}", cell, width))
+        .collect::<Vec<String>>()
+        .join(" | ")
+}
+
+/// The width of a row of columns with the given widths
+fn table_width(widths: &[usize]) -> usize {
+    widths.iter().sum::<usize>() + 3 * (widths.len() - 1)
+}
+
+/// The lines of a table with a row per day of answers and timings, followed by the grand total.
+/// The peak and total memory of each day are shown too when allocations were counted.
+fn summary_table(results: &[(u32, Result<RunResult>)]) -> Vec<String> {
+    let show_memory = results.iter().any(|(_, r)| {
+        matches!(
+            r,
+            Ok(RunResult {
+                memory: Some(_),
+                ..
+            })
+        )
+    });
+
+    let mut header = vec![
+        "Day", "Part 1", "Part 2", "Setup", "Part 1", "Part 2", "Total",
+    ];
+    let mut widths = vec![DAY_COLUMN, ANSWER_COLUMN, ANSWER_COLUMN];
+    widths.extend(&[TIME_COLUMN; 4]);
+    if show_memory {
+        header.extend(&["Peak", "Allocated"]);
+        widths.extend(&[MEMORY_COLUMN; 2]);
+    }
+    let header: Vec<String> = header.into_iter().map(String::from).collect();
+    let rule = "-".repeat(table_width(&widths));
+
+    let mut lines = vec![table_row(&header, &widths), rule.clone()];
+    let mut grand_total = Duration::default();
+    let (mut grand_peak, mut grand_allocated) = (0, 0);
+    for (day, result) in results {
+        match result {
+            Ok(r) => {
+                grand_total += r.timings.total();
+                let mut cells = vec![
+                    day.to_string(),
+                    answer_or_skipped(&r.part_1),
+                    answer_or_skipped(&r.part_2),
+                    format!("{:.2?}", r.timings.setup),
+                    format!("{:.2?}", r.timings.part_1),
+                    format!("{:.2?}", r.timings.part_2),
+                    format!("{:.2?}", r.timings.total()),
+                ];
+                if let Some(memory) = &r.memory {
+                    grand_peak = grand_peak.max(memory.peak());
+                    grand_allocated += memory.total();
+                    cells.push(format_bytes(memory.peak()));
+                    cells.push(format_bytes(memory.total()));
+                }
+                lines.push(table_row(&cells, &widths));
+            }
+            Err(err) => lines.push(format!("{:>1$} | Failed: {2}", day, DAY_COLUMN, err)),
+        }
+    }
+    lines.push(rule);
+
+    // The label spans every column before the total time, so the totals line up beneath theirs
+    let mut total_widths = vec![table_width(&widths[..6]), TIME_COLUMN];
+    let mut totals = vec!["Grand total".to_string(), format!("{:.2?}", grand_total)];
+    if show_memory {
+        total_widths.extend(&[MEMORY_COLUMN; 2]);
+        totals.push(format_bytes(grand_peak));
+        totals.push(format_bytes(grand_allocated));
+    }
+    lines.push(table_row(&totals, &total_widths));
+
+    lines
+}
+
+/// Print a row per day with its answers and timings, followed by the grand total
+pub fn print_summary(results: &[(u32, Result<RunResult>)]) {
+    summary_table(results)
+        .iter()
+        .for_each(|line| println!("{}", line));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+
+    fn day(day: u32, micros: [u64; 3]) -> (u32, Result<RunResult>) {
+        let result = RunResult::with_answers(2020, day, Some(Answer::from(514579)), None, micros);
+
+        (day, Ok(result))
+    }
+
+    /// The column at which each `|` separator falls on the line
+    fn separators(line: &str) -> Vec<usize> {
+        line.chars()
+            .enumerate()
+            .filter(|(_, c)| *c == '|')
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    const DAY_1: &str = "1721\n979\n366\n299\n675\n1456\n";
+
+    #[test]
+    fn parts_include() {
+        assert!(Parts::One.includes(1) && !Parts::One.includes(2));
+        assert!(Parts::Two.includes(2) && !Parts::Two.includes(1));
+        assert!(Parts::Both.includes(1) && Parts::Both.includes(2));
+        assert!("3".parse::<Parts>().is_err());
+    }
+
+    #[test]
+    fn run_a_single_part() {
+        let params = Params::new();
+
+        let result = run_day(2020, 1, DAY_1, &params, Parts::Two).unwrap();
+        assert_eq!(result.part_1, None);
+        assert_eq!(result.part_2, Some(Answer::from(241861950)));
+        assert_eq!(result.timings.part_1, Duration::default());
+
+        let result = run_day(2020, 1, DAY_1, &params, Parts::One).unwrap();
+        assert_eq!(result.part_1, Some(Answer::from(514579)));
+        assert_eq!(result.part_2, None);
+    }
+
+    #[test]
+    fn parse_only() {
+        let params = Params::new();
+
+        let parsed = parse_day(2020, 1, "1721\n299\n", &params).unwrap();
+        assert!(parsed.contains("1721") && parsed.contains("299"));
+        assert!(matches!(
+            parse_day(2020, 1, "1721\nabc\n", &params),
+            Err(Error::Parse { line: 2, .. })
+        ));
+        assert!(matches!(
+            parse_day(2020, 7, "", &params),
+            Err(Error::NotImplemented(2020, 7))
+        ));
+    }
+
+    #[test]
+    fn run_days_in_input_order() {
+        let inputs = || {
+            vec![
+                (10, Ok("1\n4\n".to_string())),
+                (7, Ok(String::new())),
+                (1, Ok(DAY_1.to_string())),
+                (5, Err(Error::no_solution("no input"))),
+                (6, Ok("abc\n\nab\n".to_string())),
+            ]
+        };
+
+        for &sequential in &[false, true] {
+            let results = run_days(2020, inputs(), &Params::new(), Parts::Both, sequential);
+
+            let days: Vec<u32> = results.iter().map(|(day, _)| *day).collect();
+            assert_eq!(days, vec![10, 7, 1, 5, 6]);
+            assert!(matches!(results[1].1, Err(Error::NotImplemented(2020, 7))));
+            assert!(matches!(results[3].1, Err(Error::NoSolution(_))));
+            for (day, result) in results.iter().filter(|(_, r)| r.is_ok()) {
+                assert_eq!(result.as_ref().unwrap().day, *day);
+            }
+            assert_eq!(
+                results[2].1.as_ref().unwrap().part_1,
+                Some(Answer::from(514579))
+            );
+            assert_eq!(results[4].1.as_ref().unwrap().part_1, Some(Answer::from(5)));
+        }
+    }
+
+    #[test]
+    fn print_text_lists_a_line_per_entry() {
+        let report = Answer::List(vec![
+            Answer::from("line 1: too short; no digit".to_string()),
+            Answer::from("line 3: too long".to_string()),
+        ]);
+
+        assert_eq!(
+            answer_lines(1, &Some(report)),
+            "Part 1:\n  line 1: too short; no digit\n  line 3: too long"
+        );
+        assert_eq!(answer_lines(1, &Some(Answer::List(vec![]))), "Part 1: []");
+        assert_eq!(
+            answer_lines(2, &Some(Answer::from(vec![1, 9]))),
+            "Part 2: [1, 9]"
+        );
+        assert_eq!(answer_lines(2, &None), "Part 2: -");
+    }
+
+    #[test]
+    fn summary_columns_line_up() {
+        let mut results = vec![
+            day(1, [30, 25, 1]),
+            day(2, [1_500, 20_000, 3]),
+            (3, Err(Error::no_solution("nothing"))),
+        ];
+        let lines = summary_table(&results);
+
+        assert!(lines.iter().take(4).all(|l| l.chars().count() == 102));
+        assert!(lines[5].chars().all(|c| c == '-') && lines[5].len() == 102);
+        assert_eq!(separators(&lines[0]), separators(&lines[2]));
+        assert_eq!(
+            lines[6],
+            format!("{:>87} | {:>12}", "Grand total", "21.56ms")
+        );
+        assert_eq!(separators(&lines[6])[0], separators(&lines[0])[5]);
+
+        if let (_, Ok(r)) = &mut results[0] {
+            r.memory = Some(Memory::default());
+        }
+        let lines = summary_table(&results);
+
+        assert!(lines[0].ends_with("|       Peak |  Allocated"));
+        assert!(lines.iter().take(3).all(|l| l.chars().count() == 128));
+        assert_eq!(lines[5].len(), 128);
+        assert_eq!(&separators(&lines[6])[..], &separators(&lines[0])[5..]);
+    }
+}
@@ -0,0 +1,187 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::{Error, Result};
+
+const DAY_TEMPLATE: &str = include_str!("days/template.rs.in");
+const YEAR_TEMPLATE: &str = include_str!("days/year.rs.in");
+
+/// Render the day module template for a day, with a worked-example test left to fill in. The
+/// title goes in a doc comment as well as a string literal, so it can't contain control
+/// characters such as newlines.
+pub fn render_day(year: u32, day: u32, title: &str) -> Result<String> {
+    if title.chars().any(char::is_control) {
+        return Err(Error::Format(format!(
+            "Title {:?} contains control characters",
+            title
+        )));
+    }
+
+    Ok(DAY_TEMPLATE
+        .replace("{{year}}", &year.to_string())
+        .replace("{{day}}", &day.to_string())
+        .replace("{{title:?}}", &format!("{:?}", title))
+        .replace("{{title}}", title))
+}
+
+/// Render the module for a year with no days registered yet
+pub fn render_year(year: u32) -> String {
+    YEAR_TEMPLATE.replace("{{year}}", &year.to_string())
+}
+
+/// Add a module named `{prefix}{n}` to the list passed to `macro_name!`, keeping the modules in
+/// ascending order of `n`
+fn register_module(source: &str, macro_name: &str, prefix: &str, n: u32) -> Result<String> {
+    let opening = format!("{}! {{", macro_name);
+    let start = source
+        .find(&opening)
+        .ok_or_else(|| Error::Format(format!("Could not find \"{}\"", opening)))?
+        + opening.len();
+    let end = start
+        + source[start..]
+            .find('}')
+            .ok_or_else(|| Error::Format(format!("Unterminated {}! list", macro_name)))?;
+
+    let mut modules = source[start..end]
+        .split(',')
+        .map(str::trim)
+        .filter(|m| !m.is_empty())
+        .map(|m| {
+            m.strip_prefix(prefix)
+                .and_then(|n| n.parse::<u32>().ok())
+                .ok_or_else(|| Error::Format(format!("Unexpected module \"{}\"", m)))
+        })
+        .collect::<Result<Vec<u32>>>()?;
+
+    if modules.contains(&n) {
+        return Err(Error::Format(format!(
+            "{}{} is already registered",
+            prefix, n
+        )));
+    }
+    modules.push(n);
+    modules.sort_unstable();
+
+    let list: String = modules
+        .iter()
+        .map(|n| format!("    {}{},\n", prefix, n))
+        .collect();
+
+    Ok(format!("{}\n{}{}", &source[..start], list, &source[end..]))
+}
+
+/// Add a day's module to the `register_days!` list in the source of its year's `mod.rs`
+pub fn register_day(mod_source: &str, day: u32) -> Result<String> {
+    register_module(mod_source, "register_days", "day_", day)
+}
+
+/// Add a year's module to the `register_years!` list in the source of `lib.rs`
+pub fn register_year(lib_source: &str, year: u32) -> Result<String> {
+    register_module(lib_source, "register_years", "y", year)
+}
+
+/// Generate `src/yYYYY/day_N.rs` under the crate root and register it, starting the year's
+/// module tree if this is its first day. Returns the new file's path, and refuses to overwrite an
+/// existing day.
+pub fn new_day(root: &Path, year: u32, day: u32, title: &str) -> Result<PathBuf> {
+    if !(1..=25).contains(&day) {
+        return Err(Error::Format(format!(
+            "Day {} is not between 1 and 25",
+            day
+        )));
+    }
+
+    let day_source = render_day(year, day, title)?;
+    let year_dir = root.join("src").join(format!("y{}", year));
+    let mod_path = year_dir.join("mod.rs");
+    let day_path = year_dir.join(format!("day_{}.rs", day));
+
+    if day_path.exists() {
+        return Err(Error::Format(format!("{:?} already exists", day_path)));
+    }
+
+    if !mod_path.exists() {
+        let lib_path = root.join("src").join("lib.rs");
+        let lib_source = register_year(&fs::read_to_string(&lib_path)?, year)?;
+
+        fs::create_dir_all(&year_dir)?;
+        fs::write(&mod_path, render_year(year))?;
+        fs::write(&lib_path, lib_source)?;
+    }
+
+    let mod_source = register_day(&fs::read_to_string(&mod_path)?, day)?;
+
+    fs::write(&day_path, day_source)?;
+    fs::write(&mod_path, mod_source)?;
+
+    Ok(day_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MOD_SOURCE: &str =
+        "register_days! {\n    day_1,\n    day_10,\n}\n\npub const YEAR: u32 = 2020;\n";
+
+    #[test]
+    fn escape_title_in_literal() {
+        let source = render_day(2021, 4, r#"Passport "Processing" \ Part"#).unwrap();
+
+        assert!(source.starts_with("//! Day 4: Passport \"Processing\" \\ Part\n"));
+        assert!(source.contains(r#"title: "Passport \"Processing\" \\ Part","#));
+        assert!(render_day(2021, 4, "Two\nLines").is_err());
+        assert!(render_day(2021, 4, "Tab\there").is_err());
+    }
+
+    #[test]
+    fn register_days_in_order() {
+        let source = register_day(MOD_SOURCE, 3).unwrap();
+        assert_eq!(
+            source,
+            MOD_SOURCE.replace("day_1,\n", "day_1,\n    day_3,\n")
+        );
+
+        let source = register_day(&source, 25).unwrap();
+        assert!(source.contains("    day_10,\n    day_25,\n}"));
+    }
+
+    #[test]
+    fn register_first_day() {
+        assert_eq!(
+            register_day(&render_year(2021), 1).unwrap(),
+            render_year(2021).replace("register_days! {\n}", "register_days! {\n    day_1,\n}")
+        );
+    }
+
+    #[test]
+    fn reject_duplicate_day() {
+        assert!(matches!(
+            register_day(MOD_SOURCE, 10),
+            Err(Error::Format(_))
+        ));
+        assert!(matches!(
+            register_day("mod day_1;", 2),
+            Err(Error::Format(_))
+        ));
+        assert!(matches!(
+            register_day("register_days! {\n    day_x,\n}", 2),
+            Err(Error::Format(_))
+        ));
+    }
+
+    #[test]
+    fn register_years_in_order() {
+        let lib = "register_years! {\n    y2020,\n}\n";
+
+        assert_eq!(
+            register_year(lib, 2015).unwrap(),
+            "register_years! {\n    y2015,\n    y2020,\n}\n"
+        );
+        assert_eq!(
+            register_year("register_years! {}\n", 2021).unwrap(),
+            "register_years! {\n    y2021,\n}\n"
+        );
+        assert!(register_year(lib, 2020).is_err());
+    }
+}
@@ -0,0 +1,611 @@
+use std::convert::TryFrom;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use regex::Regex;
+use toml::value::{Table, Value};
+
+use crate::days::{registration, Answer, Params, DEFAULT_YEAR};
+use crate::error::{Error, Result};
+use crate::fetch::InputCache;
+use crate::runner::{run_day, Parts};
+use crate::toml_file::{parse_table, TomlFile};
+
+/// How the server responded to a submitted answer
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Incorrect,
+    /// Another answer was submitted too recently, so this one wasn't checked
+    Wait(Duration),
+    /// The part has already been solved
+    AlreadySolved,
+    Unrecognised,
+}
+
+impl Verdict {
+    fn key(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+            Verdict::Incorrect => "incorrect",
+            Verdict::Wait(_) => "wait",
+            Verdict::AlreadySolved => "already_solved",
+            Verdict::Unrecognised => "unrecognised",
+        }
+    }
+
+    fn from_key(key: &str, wait: Duration) -> Option<Verdict> {
+        match key {
+            "correct" => Some(Verdict::Correct),
+            "too_high" => Some(Verdict::TooHigh),
+            "too_low" => Some(Verdict::TooLow),
+            "incorrect" => Some(Verdict::Incorrect),
+            "wait" => Some(Verdict::Wait(wait)),
+            "already_solved" => Some(Verdict::AlreadySolved),
+            "unrecognised" => Some(Verdict::Unrecognised),
+            _ => None,
+        }
+    }
+
+    /// Whether the server checked the answer and rejected it
+    pub fn is_wrong(&self) -> bool {
+        matches!(
+            self,
+            Verdict::TooHigh | Verdict::TooLow | Verdict::Incorrect
+        )
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "incorrect, too high"),
+            Verdict::TooLow => write!(f, "incorrect, too low"),
+            Verdict::Incorrect => write!(f, "incorrect"),
+            Verdict::Wait(wait) => write!(f, "rate limited, wait {}s", wait.as_secs()),
+            Verdict::AlreadySolved => write!(f, "already solved"),
+            Verdict::Unrecognised => write!(f, "unrecognised response"),
+        }
+    }
+}
+
+/// Read the verdict from the page the server returns for a submitted answer
+pub fn parse_response(page: &str) -> Verdict {
+    lazy_static! {
+        static ref WAIT_RE: Regex =
+            Regex::new(r"(?:(?P<min>[0-9]+)m )?(?P<sec>[0-9]+)s left to wait").unwrap();
+    }
+
+    if page.contains("That's the right answer") {
+        Verdict::Correct
+    } else if page.contains("answer is too high") {
+        Verdict::TooHigh
+    } else if page.contains("answer is too low") {
+        Verdict::TooLow
+    } else if page.contains("That's not the right answer") {
+        Verdict::Incorrect
+    } else if let Some(caps) = WAIT_RE.captures(page) {
+        // A wait too long to record in the attempt log can't be a real one
+        let minutes = caps
+            .name("min")
+            .map_or(Some(0), |m| m.as_str().parse::<u64>().ok());
+        let seconds = caps["sec"].parse::<u64>().ok();
+
+        minutes
+            .and_then(|m| m.checked_mul(60))
+            .zip(seconds)
+            .and_then(|(m, s)| m.checked_add(s))
+            .filter(|&secs| i64::try_from(secs).is_ok())
+            .map_or(Verdict::Unrecognised, |secs| {
+                Verdict::Wait(Duration::from_secs(secs))
+            })
+    } else if page.contains("Did you already complete it") {
+        Verdict::AlreadySolved
+    } else {
+        Verdict::Unrecognised
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attempt {
+    pub year: u32,
+    pub day: u32,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// When the answer was submitted, in seconds since the Unix epoch
+    pub submitted_at: u64,
+}
+
+/// Every answer submitted so far, stored as a TOML array of tables such as
+///
+/// ```toml
+/// [[attempt]]
+/// year = 2020
+/// day = 1
+/// part = 1
+/// answer = "514579"
+/// verdict = "correct"
+/// submitted_at = 1607749200
+/// ```
+///
+/// `wait` attempts also record the `wait_secs` the server asked for. Attempts without a `year`
+/// are for 2020.
+#[derive(Debug, Default)]
+pub struct AttemptLog {
+    attempts: Vec<Attempt>,
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// The integer under the key, which must be in the range of `T`
+fn integer<T: TryFrom<i64>>(table: &Table, key: &str) -> Result<T> {
+    table
+        .get(key)
+        .and_then(Value::as_integer)
+        .and_then(|v| T::try_from(v).ok())
+        .ok_or_else(|| Error::Format(format!("Missing or invalid \"{}\" in attempt", key)))
+}
+
+/// A count of seconds as a TOML integer, which must fit in an `i64`
+fn seconds(secs: u64, key: &str) -> Result<Value> {
+    i64::try_from(secs)
+        .map(Value::Integer)
+        .map_err(|_| Error::Format(format!("\"{}\" of {} is too large to record", key, secs)))
+}
+
+impl TomlFile for AttemptLog {
+    fn parse(s: &str) -> Result<Self> {
+        let table = parse_table(s, "attempts")?;
+
+        let attempts = match table.get("attempt") {
+            Some(Value::Array(items)) => items
+                .iter()
+                .map(|item| {
+                    let item = item
+                        .as_table()
+                        .ok_or_else(|| Error::Format("An attempt is not a table".to_string()))?;
+                    let verdict = item.get("verdict").and_then(Value::as_str).unwrap_or("");
+                    let wait = if verdict == "wait" || item.contains_key("wait_secs") {
+                        Duration::from_secs(integer(item, "wait_secs")?)
+                    } else {
+                        Duration::default()
+                    };
+
+                    Ok(Attempt {
+                        year: match item.get("year") {
+                            Some(_) => integer(item, "year")?,
+                            None => DEFAULT_YEAR,
+                        },
+                        day: integer(item, "day")?,
+                        part: integer(item, "part")?,
+                        answer: item
+                            .get("answer")
+                            .and_then(Value::as_str)
+                            .ok_or_else(|| Error::Format("Missing answer in attempt".to_string()))?
+                            .to_string(),
+                        verdict: Verdict::from_key(verdict, wait).ok_or_else(|| {
+                            Error::Format(format!("Invalid verdict \"{}\"", verdict))
+                        })?,
+                        submitted_at: integer(item, "submitted_at")?,
+                    })
+                })
+                .collect::<Result<_>>()?,
+            Some(_) => return Err(Error::Format("\"attempt\" is not an array".to_string())),
+            None => Vec::new(),
+        };
+
+        Ok(AttemptLog { attempts })
+    }
+}
+
+impl AttemptLog {
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let attempts: Vec<Value> = self
+            .attempts
+            .iter()
+            .map(|a| {
+                let mut table = Table::new();
+                table.insert("year".to_string(), Value::Integer(a.year.into()));
+                table.insert("day".to_string(), Value::Integer(a.day.into()));
+                table.insert("part".to_string(), Value::Integer(a.part.into()));
+                table.insert("answer".to_string(), Value::String(a.answer.clone()));
+                table.insert(
+                    "verdict".to_string(),
+                    Value::String(a.verdict.key().to_string()),
+                );
+                if let Verdict::Wait(wait) = a.verdict {
+                    table.insert(
+                        "wait_secs".to_string(),
+                        seconds(wait.as_secs(), "wait_secs")?,
+                    );
+                }
+                table.insert(
+                    "submitted_at".to_string(),
+                    seconds(a.submitted_at, "submitted_at")?,
+                );
+
+                Ok(Value::Table(table))
+            })
+            .collect::<Result<_>>()?;
+
+        let mut table = Table::new();
+        table.insert("attempt".to_string(), Value::Array(attempts));
+        fs::write(path, Value::Table(table).to_string())?;
+
+        Ok(())
+    }
+
+    pub fn record(&mut self, attempt: Attempt) {
+        self.attempts.push(attempt);
+    }
+
+    pub fn attempts(&self) -> &[Attempt] {
+        &self.attempts
+    }
+
+    /// The reason not to submit the answer, if the log shows it can't be right or the server
+    /// would rate limit it at time `now`
+    pub fn refusal(
+        &self,
+        year: u32,
+        day: u32,
+        part: u8,
+        answer: &Answer,
+        now: u64,
+    ) -> Option<String> {
+        let rendered = answer.to_string();
+        let value = match answer {
+            Answer::Integer(v) => Some(*v as i128),
+            Answer::BigInteger(v) => Some(*v),
+            _ => None,
+        };
+        let previous = || {
+            self.attempts
+                .iter()
+                .filter(move |a| a.year == year && a.day == day && a.part == part)
+        };
+
+        if let Some(a) = previous().find(|a| a.verdict == Verdict::Correct) {
+            return Some(format!("Part {} is already solved with {}", part, a.answer));
+        }
+        if let Some(a) = previous().find(|a| a.answer == rendered && a.verdict.is_wrong()) {
+            return Some(format!(
+                "{} was already rejected as {}",
+                rendered, a.verdict
+            ));
+        }
+        if let Some(value) = value {
+            let bound = |verdict: Verdict| {
+                previous()
+                    .filter(move |a| a.verdict == verdict)
+                    .filter_map(|a| a.answer.parse::<i128>().ok())
+            };
+
+            if let Some(high) = bound(Verdict::TooHigh).min().filter(|&h| value >= h) {
+                return Some(format!("{} is too high, {} already was", value, high));
+            }
+            if let Some(low) = bound(Verdict::TooLow).max().filter(|&l| value <= l) {
+                return Some(format!("{} is too low, {} already was", value, low));
+            }
+        }
+
+        // The server rate limits submissions across every day
+        self.attempts
+            .iter()
+            .filter_map(|a| match a.verdict {
+                Verdict::Wait(wait) => Some(a.submitted_at.saturating_add(wait.as_secs())),
+                _ => None,
+            })
+            .max()
+            .filter(|&until| until > now)
+            .map(|until| format!("Rate limited, wait another {}s", until - now))
+    }
+}
+
+/// The outcome of `submit_part`
+#[derive(Debug, Clone, PartialEq)]
+pub enum Submission {
+    /// The attempt log shows the answer would be wasted, so it wasn't sent
+    Refused { answer: Answer, reason: String },
+    /// The server gave its verdict on the answer, which was added to the attempt log
+    Submitted { answer: Answer, verdict: Verdict },
+}
+
+/// Solve the part and submit its answer at time `now`, unless the attempt log at `log_path`
+/// shows it would be wasted. Each answer sent is recorded in the log.
+pub fn submit_part(
+    year: u32,
+    day: u32,
+    part: u8,
+    log_path: &Path,
+    cache: &InputCache,
+    now: u64,
+) -> Result<Submission> {
+    let parts = match part {
+        1 => Parts::One,
+        2 => Parts::Two,
+        _ => return Err(Error::Param(format!("there is no part {}", part))),
+    };
+    let mut log = AttemptLog::load_or_default(log_path)?;
+
+    // Check the day can run before reading, or downloading, its input
+    registration(year, day).ok_or(Error::NotImplemented(year, day))?;
+    let result = run_day(year, day, &cache.get(year, day)?, &Params::new(), parts)?;
+    let answer = if part == 1 {
+        result.part_1
+    } else {
+        result.part_2
+    }
+    .ok_or_else(|| Error::no_solution(format!("part {} produced no answer", part)))?;
+
+    if let Some(reason) = log.refusal(year, day, part, &answer, now) {
+        return Ok(Submission::Refused { answer, reason });
+    }
+
+    let page = cache
+        .fetcher()
+        .submit_answer(year, day, part, &answer.to_string())?;
+    let verdict = parse_response(&page);
+    log.record(Attempt {
+        year,
+        day,
+        part,
+        answer: answer.to_string(),
+        verdict: verdict.clone(),
+        submitted_at: now,
+    });
+    log.save(log_path)?;
+
+    Ok(Submission::Submitted { answer, verdict })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fetch::{serve_once, Fetcher};
+
+    fn attempt(answer: &str, verdict: Verdict, submitted_at: u64) -> Attempt {
+        Attempt {
+            year: 2020,
+            day: 1,
+            part: 1,
+            answer: answer.to_string(),
+            verdict,
+            submitted_at,
+        }
+    }
+
+    #[test]
+    fn parse_verdicts() {
+        assert_eq!(
+            parse_response("<p>That's the right answer! You are one gold star closer.</p>"),
+            Verdict::Correct
+        );
+        assert_eq!(
+            parse_response("<p>That's not the right answer; your answer is too high.</p>"),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            parse_response("<p>That's not the right answer; your answer is too low.</p>"),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            parse_response("<p>That's not the right answer. If you're stuck...</p>"),
+            Verdict::Incorrect
+        );
+        assert_eq!(
+            parse_response("<p>You gave an answer too recently. You have 4m 35s left to wait.</p>"),
+            Verdict::Wait(Duration::from_secs(275))
+        );
+        assert_eq!(
+            parse_response("<p>You have 35s left to wait.</p>"),
+            Verdict::Wait(Duration::from_secs(35))
+        );
+        assert_eq!(
+            parse_response(
+                "<p>You don't seem to be solving the right level. Did you already complete it?</p>"
+            ),
+            Verdict::AlreadySolved
+        );
+        assert_eq!(parse_response("<html></html>"), Verdict::Unrecognised);
+    }
+
+    #[test]
+    fn reject_waits_too_long_to_count() {
+        assert_eq!(
+            parse_response("<p>You have 99999999999999999999s left to wait.</p>"),
+            Verdict::Unrecognised
+        );
+        assert_eq!(
+            parse_response("<p>You have 99999999999999999999m 1s left to wait.</p>"),
+            Verdict::Unrecognised
+        );
+        assert_eq!(
+            parse_response("<p>You have 307445734561825860m 15s left to wait.</p>"),
+            Verdict::Unrecognised
+        );
+        assert_eq!(
+            parse_response("<p>You have 153722867280912930m 8s left to wait.</p>"),
+            Verdict::Unrecognised
+        );
+        assert_eq!(
+            parse_response("<p>You have 153722867280912930m 7s left to wait.</p>"),
+            Verdict::Wait(Duration::from_secs(i64::MAX as u64))
+        );
+    }
+
+    #[test]
+    fn log_round_trip() {
+        let mut log = AttemptLog::default();
+        log.record(attempt("100", Verdict::TooHigh, 10));
+        log.record(attempt("50", Verdict::Wait(Duration::from_secs(60)), 20));
+
+        let path = std::env::temp_dir().join(format!("aoc2020-attempts-{}", std::process::id()));
+        log.save(&path).unwrap();
+        let loaded = AttemptLog::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.attempts(), log.attempts());
+
+        log.record(attempt(
+            "7",
+            Verdict::Wait(Duration::from_secs(u64::MAX)),
+            30,
+        ));
+        assert!(matches!(log.save(&path), Err(Error::Format(_))));
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn reject_out_of_range_fields() {
+        let log = |fields: &str| {
+            AttemptLog::parse(&format!(
+                "[[attempt]]\nanswer = \"5\"\nsubmitted_at = 0\n{}",
+                fields
+            ))
+        };
+
+        assert!(log("day = 1\npart = 1\nverdict = \"wait\"\nwait_secs = 30").is_ok());
+        for fields in &[
+            "day = 1\npart = 257\nverdict = \"correct\"",
+            "day = -1\npart = 1\nverdict = \"correct\"",
+            "day = 4294967297\npart = 1\nverdict = \"correct\"",
+            "year = -2020\nday = 1\npart = 1\nverdict = \"correct\"",
+            "day = 1\npart = 1\nverdict = \"wait\"\nwait_secs = -1",
+            "day = 1\npart = 1\nverdict = \"wait\"",
+            "day = 1\npart = 1\nverdict = \"correct\"\nwait_secs = \"soon\"",
+        ] {
+            assert!(
+                matches!(log(fields), Err(Error::Format(_))),
+                "{:?} should be rejected",
+                fields
+            );
+        }
+    }
+
+    #[test]
+    fn refuse_known_wrong_answers() {
+        let mut log = AttemptLog::default();
+        log.record(attempt("100", Verdict::TooHigh, 0));
+        log.record(attempt("10", Verdict::TooLow, 0));
+        log.record(attempt("42", Verdict::Incorrect, 0));
+
+        assert!(log.refusal(2020, 1, 1, &Answer::from(42), 0).is_some());
+        assert!(log.refusal(2020, 1, 1, &Answer::from(150), 0).is_some());
+        assert!(log.refusal(2020, 1, 1, &Answer::from(5), 0).is_some());
+        assert!(log.refusal(2020, 1, 1, &Answer::from(50), 0).is_none());
+        assert!(log.refusal(2020, 1, 2, &Answer::from(42), 0).is_none());
+        assert!(log.refusal(2019, 1, 1, &Answer::from(42), 0).is_none());
+    }
+
+    #[test]
+    fn attempts_default_to_2020() {
+        let log = AttemptLog::parse(
+            "[[attempt]]\nday = 1\npart = 1\nanswer = \"5\"\nverdict = \"correct\"\nsubmitted_at = 0",
+        )
+        .unwrap();
+
+        assert_eq!(log.attempts()[0].year, 2020);
+    }
+
+    #[test]
+    fn refuse_solved_parts() {
+        let mut log = AttemptLog::default();
+        log.record(attempt("514579", Verdict::Correct, 0));
+
+        assert!(log.refusal(2020, 1, 1, &Answer::from(514579), 0).is_some());
+    }
+
+    #[test]
+    fn refuse_while_rate_limited() {
+        let mut log = AttemptLog::default();
+        log.record(attempt("42", Verdict::Wait(Duration::from_secs(60)), 100));
+
+        assert!(log.refusal(2020, 2, 1, &Answer::from(7), 130).is_some());
+        assert!(log.refusal(2020, 2, 1, &Answer::from(7), 160).is_none());
+
+        log.record(attempt(
+            "42",
+            Verdict::Wait(Duration::from_secs(u64::MAX)),
+            100,
+        ));
+        assert!(log.refusal(2020, 2, 1, &Answer::from(7), 160).is_some());
+    }
+
+    fn submit_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc2020-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("2020")).unwrap();
+        fs::write(
+            dir.join("2020").join("day_1.txt"),
+            "1721\n979\n366\n299\n675\n1456\n",
+        )
+        .unwrap();
+
+        dir
+    }
+
+    #[test]
+    fn submit_and_record_the_verdict() {
+        let dir = submit_dir("submit");
+        let log_path = dir.join("attempts.toml");
+        let (base_url, server) = serve_once("200 OK", "<p>That's the right answer!</p>");
+        let cache = InputCache::new(&dir, Fetcher::new(&base_url, Some("abc".to_string())));
+
+        assert_eq!(
+            submit_part(2020, 1, 1, &log_path, &cache, 50).unwrap(),
+            Submission::Submitted {
+                answer: Answer::from(514579),
+                verdict: Verdict::Correct
+            }
+        );
+        assert!(server.join().unwrap().ends_with("level=1&answer=514579"));
+        assert_eq!(
+            AttemptLog::load(&log_path).unwrap().attempts(),
+            &[Attempt {
+                submitted_at: 50,
+                ..attempt("514579", Verdict::Correct, 0)
+            }]
+        );
+
+        // The server has gone, so the log has to refuse the second submission
+        assert!(matches!(
+            submit_part(2020, 1, 1, &log_path, &cache, 60).unwrap(),
+            Submission::Refused { .. }
+        ));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn submit_only_implemented_days() {
+        let dir = submit_dir("submit-unimplemented");
+        let cache = InputCache::new(&dir, Fetcher::new("http://127.0.0.1:1", None));
+        let log_path = dir.join("attempts.toml");
+
+        assert!(matches!(
+            submit_part(2020, 7, 1, &log_path, &cache, 0),
+            Err(Error::NotImplemented(2020, 7))
+        ));
+        assert!(matches!(
+            submit_part(2020, 1, 3, &log_path, &cache, 0),
+            Err(Error::Param(_))
+        ));
+        assert!(!dir.join("2020").join("day_7.txt").exists());
+        assert!(!log_path.exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,144 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use toml::value::Table;
+use toml::Value;
+
+use crate::days::DEFAULT_YEAR;
+use crate::error::{Error, Result};
+
+/// A file format stored as TOML, such as the expected answers or a benchmark baseline
+pub trait TomlFile: Default {
+    fn parse(s: &str) -> Result<Self>;
+
+    fn load(path: &Path) -> Result<Self> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    /// Load the file at the path, or start from the defaults if there isn't one yet
+    fn load_or_default(path: &Path) -> Result<Self> {
+        if path.exists() {
+            Self::load(path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+}
+
+/// Parse a TOML document that must be a table, naming what it should hold in the error if not
+pub fn parse_table(s: &str, contents: &str) -> Result<Table> {
+    match s.parse::<Value>() {
+        Ok(Value::Table(t)) => Ok(t),
+        Ok(_) => Err(Error::Format(format!("Expected a table of {}", contents))),
+        Err(e) => Err(Error::Format(e.to_string())),
+    }
+}
+
+/// The value under a key, which must be a table
+pub fn sub_table<'a>(key: &str, value: &'a Value) -> Result<&'a Table> {
+    value
+        .as_table()
+        .ok_or_else(|| Error::Format(format!("\"{}\" is not a table", key)))
+}
+
+/// The number in a key made of a prefix and a number, such as `day_1`
+pub fn numbered_key(key: &str, prefix: &str) -> Result<u32> {
+    key.strip_prefix(prefix)
+        .and_then(|n| n.parse::<u32>().ok())
+        .ok_or_else(|| Error::Format(format!("Invalid key \"{}\"", key)))
+}
+
+/// A table holding one day's entries, with the year, day and key it was found under
+pub struct DayTable<'a> {
+    pub year: u32,
+    pub day: u32,
+    pub key: String,
+    pub table: &'a Table,
+}
+
+/// The day tables of a file keyed by year and then day, as in `[2020.day_1]`. Day tables at the
+/// top level, as in `[day_1]`, belong to `DEFAULT_YEAR`.
+pub fn day_tables(table: &Table) -> Result<Vec<DayTable<'_>>> {
+    let mut days = Vec::new();
+    for (key, value) in table.iter() {
+        if key.starts_with("day_") {
+            days.push(DayTable {
+                year: DEFAULT_YEAR,
+                day: numbered_key(key, "day_")?,
+                key: key.clone(),
+                table: sub_table(key, value)?,
+            });
+            continue;
+        }
+
+        let year = numbered_key(key, "")?;
+        for (day_key, day_value) in sub_table(key, value)?.iter() {
+            let full_key = format!("{}.{}", key, day_key);
+            days.push(DayTable {
+                year,
+                day: numbered_key(day_key, "day_")?,
+                table: sub_table(&full_key, day_value)?,
+                key: full_key,
+            });
+        }
+    }
+
+    Ok(days)
+}
+
+/// Nest tables keyed by year and day as `[YEAR.day_DAY]`, the layout `day_tables` reads
+pub fn nest_day_tables(days: impl IntoIterator<Item = ((u32, u32), Table)>) -> Table {
+    let mut years: BTreeMap<u32, Table> = BTreeMap::new();
+    for ((year, day), table) in days {
+        years
+            .entry(year)
+            .or_default()
+            .insert(format!("day_{}", day), Value::Table(table));
+    }
+
+    years
+        .into_iter()
+        .map(|(year, days)| (year.to_string(), Value::Table(days)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_keys_and_tables() {
+        let table = parse_table("[day_12]\npart_1 = 3", "days").unwrap();
+
+        assert_eq!(numbered_key("day_12", "day_").unwrap(), 12);
+        assert!(numbered_key("day_x", "day_").is_err());
+        assert!(numbered_key("part_1", "day_").is_err());
+        assert!(sub_table("day_12", &table["day_12"]).is_ok());
+        assert!(sub_table("part_1", &table["day_12"]["part_1"]).is_err());
+        assert!(parse_table("[day_1", "days").is_err());
+    }
+
+    #[test]
+    fn find_day_tables_by_year() {
+        let table = parse_table("[day_1]\na = 1\n[2019.day_3]\nb = 2\n", "days").unwrap();
+        let days: Vec<_> = day_tables(&table)
+            .unwrap()
+            .into_iter()
+            .map(|d| (d.year, d.day, d.key))
+            .collect();
+
+        assert_eq!(
+            days,
+            vec![
+                (2019, 3, "2019.day_3".to_string()),
+                (2020, 1, "day_1".to_string())
+            ]
+        );
+        assert!(day_tables(&parse_table("[y2019.day_3]\nb = 2", "days").unwrap()).is_err());
+        assert!(day_tables(&parse_table("[2019]\nday_3 = 2", "days").unwrap()).is_err());
+
+        let nested = nest_day_tables(vec![((2019, 3), Table::new())]);
+        assert_eq!(day_tables(&nested).unwrap()[0].key, "2019.day_3");
+    }
+}
@@ -0,0 +1,273 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::check::{print_checks, ExpectedAnswers, PartCheck};
+use crate::days::{Answer, Params};
+use crate::error::Result;
+use crate::input::read_input;
+use crate::runner::{print_result, run_day, Parts, RunResult};
+use crate::toml_file::TomlFile;
+
+/// Polls the modification times of a set of files
+#[derive(Debug)]
+pub struct Watcher {
+    paths: Vec<PathBuf>,
+    modified: Vec<Option<SystemTime>>,
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+impl Watcher {
+    pub fn new(paths: Vec<PathBuf>) -> Watcher {
+        let modified = paths.iter().map(|p| modified_time(p)).collect();
+
+        Watcher { paths, modified }
+    }
+
+    /// Whether any file has been modified, created or removed since the last call
+    pub fn changed(&mut self) -> bool {
+        let modified: Vec<_> = self.paths.iter().map(|p| modified_time(p)).collect();
+        let changed = modified != self.modified;
+        self.modified = modified;
+
+        changed
+    }
+
+    /// Block until a file changes, checking every `interval`
+    pub fn wait(&mut self, interval: Duration) {
+        while !self.changed() {
+            thread::sleep(interval);
+        }
+    }
+}
+
+fn answer_change(part: u8, previous: &Option<Answer>, current: &Option<Answer>) -> String {
+    let render = |a: &Option<Answer>| a.as_ref().map_or("-".to_string(), Answer::to_string);
+
+    if previous == current {
+        format!("Part {}: {} (unchanged)", part, render(current))
+    } else {
+        format!("Part {}: {} -> {}", part, render(previous), render(current))
+    }
+}
+
+fn timing_change(phase: &str, previous: Duration, current: Duration) -> String {
+    let change = if previous.as_nanos() == 0 {
+        0.0
+    } else {
+        (current.as_secs_f64() - previous.as_secs_f64()) / previous.as_secs_f64() * 100.0
+    };
+
+    format!(
+        "{}: {:.2?} -> {:.2?} ({:+.1}%)",
+        phase, previous, current, change
+    )
+}
+
+/// A line per answer and phase timing describing how it changed between two runs
+pub fn diff_results(previous: &RunResult, current: &RunResult) -> Vec<String> {
+    vec![
+        answer_change(1, &previous.part_1, &current.part_1),
+        answer_change(2, &previous.part_2, &current.part_2),
+        timing_change("Setup", previous.timings.setup, current.timings.setup),
+        timing_change("Part 1", previous.timings.part_1, current.timings.part_1),
+        timing_change("Part 2", previous.timings.part_2, current.timings.part_2),
+        timing_change("Total", previous.timings.total(), current.timings.total()),
+    ]
+}
+
+/// A day that is re-run whenever its input or expected answers change, keeping the last
+/// successful run to compare the next one with
+pub struct DayWatch {
+    year: u32,
+    day: u32,
+    input_path: PathBuf,
+    check_path: Option<PathBuf>,
+    params: Params,
+    parts: Parts,
+    previous: Option<RunResult>,
+}
+
+/// What happened when a watched day was run
+#[derive(Debug)]
+pub struct WatchRun {
+    pub result: Result<RunResult>,
+    /// How the answers and timings moved since the last successful run, if there was one
+    pub changes: Option<Vec<String>>,
+    /// The run checked against the expected answers as they are now, if there is a file of them
+    /// and the run succeeded
+    pub checks: Option<Result<Vec<PartCheck>>>,
+}
+
+impl DayWatch {
+    pub fn new(
+        year: u32,
+        day: u32,
+        input_path: PathBuf,
+        check_path: Option<PathBuf>,
+        params: &Params,
+        parts: Parts,
+    ) -> DayWatch {
+        DayWatch {
+            year,
+            day,
+            input_path,
+            check_path,
+            params: params.clone(),
+            parts,
+            previous: None,
+        }
+    }
+
+    /// The files whose changes trigger a new run
+    pub fn paths(&self) -> Vec<PathBuf> {
+        std::iter::once(self.input_path.clone())
+            .chain(self.check_path.clone())
+            .collect()
+    }
+
+    /// Read the input and run the day, reloading the expected answers to check it against
+    pub fn run(&mut self) -> WatchRun {
+        let result = read_input(&self.input_path)
+            .and_then(|input| run_day(self.year, self.day, &input, &self.params, self.parts));
+
+        let (changes, checks) = match &result {
+            Ok(r) => (
+                self.previous.as_ref().map(|p| diff_results(p, r)),
+                self.check_path
+                    .as_ref()
+                    .map(|path| ExpectedAnswers::load(path).map(|e| e.check(r))),
+            ),
+            Err(_) => (None, None),
+        };
+        if let Ok(r) = &result {
+            self.previous = Some(r.clone());
+        }
+
+        WatchRun {
+            result,
+            changes,
+            checks,
+        }
+    }
+}
+
+/// Run the day, then again each time its input or expected answers change, showing how the
+/// answers and timings moved since the last successful run
+pub fn watch_day(mut watch: DayWatch) -> ! {
+    let mut watcher = Watcher::new(watch.paths());
+
+    loop {
+        println!("Running day {}\n", watch.day);
+        let run = watch.run();
+
+        match (&run.result, &run.changes) {
+            (Ok(_), Some(changes)) => {
+                println!("Changes since the last run:");
+                changes.iter().for_each(|line| println!("{}", line));
+            }
+            (Ok(r), None) => print_result(r),
+            (Err(err), _) => eprintln!("Failed to run day {}: {}", watch.day, err),
+        }
+
+        match (&run.checks, &watch.check_path) {
+            (Some(Ok(checks)), _) => {
+                println!();
+                print_checks(checks);
+            }
+            (Some(Err(err)), Some(path)) => {
+                eprintln!("Failed to load expected answers from {:?}: {}", path, err)
+            }
+            _ => {}
+        }
+
+        println!("\nWatching {:?} for changes...\n", watch.input_path);
+        watcher.wait(Duration::from_millis(500));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::Outcome;
+    use std::env;
+    use std::fs::File;
+
+    fn result(part_2: i64, setup_micros: u64) -> RunResult {
+        RunResult::with_answers(
+            2020,
+            1,
+            Some(Answer::from(5)),
+            Some(Answer::from(part_2)),
+            [setup_micros, 10, 10],
+        )
+    }
+
+    #[test]
+    fn diff_answers_and_timings() {
+        let diff = diff_results(&result(-87, 100), &result(8, 50));
+
+        assert_eq!(diff[0], "Part 1: 5 (unchanged)");
+        assert_eq!(diff[1], "Part 2: -87 -> 8");
+        assert_eq!(diff[2], "Setup: 100.00µs -> 50.00µs (-50.0%)");
+    }
+
+    #[test]
+    fn detect_modified_files() {
+        let path = env::temp_dir().join(format!("aoc2020-watch-{}", std::process::id()));
+        fs::write(&path, "1\n").unwrap();
+
+        let mut watcher = Watcher::new(vec![path.clone()]);
+        assert!(!watcher.changed());
+
+        let file = File::options().write(true).open(&path).unwrap();
+        file.set_modified(SystemTime::now() + Duration::from_secs(60))
+            .unwrap();
+        assert!(watcher.changed());
+        assert!(!watcher.changed());
+
+        fs::remove_file(&path).unwrap();
+        assert!(watcher.changed());
+    }
+
+    #[test]
+    fn compare_each_run_with_the_last_success() {
+        let dir = env::temp_dir().join(format!("aoc2020-watch-day-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let (input, check) = (dir.join("day_1.txt"), dir.join("answers.toml"));
+        fs::write(&input, "1721\n979\n366\n299\n675\n1456\n").unwrap();
+        fs::write(&check, "[day_1]\npart_1 = 514579\n").unwrap();
+
+        let mut watch = DayWatch::new(
+            2020,
+            1,
+            input.clone(),
+            Some(check.clone()),
+            &Params::new(),
+            Parts::One,
+        );
+        assert_eq!(watch.paths(), vec![input.clone(), check.clone()]);
+
+        let first = watch.run();
+        assert_eq!(first.result.unwrap().part_1, Some(Answer::from(514579)));
+        assert!(first.changes.is_none());
+        assert!(matches!(first.checks, Some(Ok(checks)) if checks[0].outcome == Outcome::Pass));
+
+        fs::write(&input, "abc\n").unwrap();
+        let failed = watch.run();
+        assert!(failed.result.is_err());
+        assert!(failed.changes.is_none() && failed.checks.is_none());
+
+        fs::write(&input, "2000\n20\n").unwrap();
+        fs::write(&check, "[day_1\n").unwrap();
+        let second = watch.run();
+        assert_eq!(second.changes.unwrap()[0], "Part 1: 514579 -> 40000");
+        assert!(matches!(second.checks, Some(Err(_))));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,303 @@
+//! Day 1: Report Repair
+
+use crate::days::{Answer, Challenge, Params, Registration};
+use crate::error::{Error, Result};
+use crate::ksum::{k_sum, k_sum_all};
+
+use itertools::Itertools;
+use std::convert::TryFrom;
+use std::fmt::Debug;
+use std::iter;
+
+/// Finds the expense report entries that sum to a target, by default a pair for part 1 and a
+/// triple for part 2 summing to 2020. The `target` and `k` parameters change the sum and the
+/// size of part 1's tuple, with part 2 always looking for one more entry, and `all` reports how
+/// many distinct tuples there are and each of them instead of the first.
+pub struct Day1<'a> {
+    /// The entries that could be part of a tuple, read as `i64`s and widened so that no sum of
+    /// them can overflow
+    data: Option<Vec<i128>>,
+    input: &'a str,
+    target: i128,
+    k: usize,
+    all: bool,
+}
+
+impl<'a> Day1<'a> {
+    pub fn build(input: &'a str) -> Box<Day1<'a>> {
+        Box::new(Day1 {
+            data: None,
+            input,
+            target: 2020,
+            k: 2,
+            all: false,
+        })
+    }
+}
+
+pub const REGISTRATION: Registration = Registration {
+    day: 1,
+    title: "Report Repair",
+    build: |input| Day1::build(input),
+};
+
+/// A combination of `size` entries that sums to the target
+pub fn get_combinations(nums: &[i128], size: usize, target: i128) -> Result<Vec<i128>> {
+    k_sum(nums, size, target)
+        .ok_or_else(|| Error::no_solution(format!("no {} entries sum to {}", size, target)))
+}
+
+/// The product of the entries, or an overflow error if it doesn't fit in an `i128`
+pub fn product(nums: &[i128]) -> Result<Answer> {
+    nums.iter()
+        .try_fold(1i128, |acc, &n| acc.checked_mul(n))
+        .map(Answer::from)
+        .ok_or_else(|| Error::Overflow(format!("{} is too large", nums.iter().join(" * "))))
+}
+
+/// How many distinct combinations of `size` entries sum to the target and what they are, as
+/// `[count, [[entries...], product]...]`
+pub fn describe_all_combinations(nums: &[i128], size: usize, target: i128) -> Result<Answer> {
+    let tuples = k_sum_all(nums, size, target);
+    if tuples.is_empty() {
+        return Err(Error::no_solution(format!(
+            "no {} entries sum to {}",
+            size, target
+        )));
+    }
+
+    let count = Answer::from(tuples.len());
+    iter::once(Ok(count))
+        .chain(tuples.iter().map(|t| {
+            let entries = Answer::List(t.iter().map(|&n| Answer::from(n)).collect());
+            Ok(Answer::List(vec![entries, product(t)?]))
+        }))
+        .collect::<Result<Vec<Answer>>>()
+        .map(Answer::List)
+}
+
+impl Day1<'_> {
+    fn solve(&self, data: &[i128], size: usize) -> Result<Answer> {
+        if self.all {
+            describe_all_combinations(data, size, self.target)
+        } else {
+            get_combinations(data, size, self.target).and_then(|nums| product(&nums))
+        }
+    }
+}
+
+impl Challenge<'_> for Day1<'_> {
+    fn configure(&mut self, params: &Params) -> Result<()> {
+        params.expect_only(&["target", "k", "all"])?;
+
+        if let Some(target) = params.get::<i64>("target")? {
+            self.target = target.into();
+        }
+        if let Some(k) = params.get("k")? {
+            if k == 0 {
+                return Err(Error::Param("k must be at least 1".to_string()));
+            }
+            if k == usize::MAX {
+                return Err(Error::Param(format!(
+                    "k must be under {} so part 2 can look for k + 1 entries",
+                    usize::MAX
+                )));
+            }
+            self.k = k;
+        }
+        if let Some(all) = params.get("all")? {
+            self.all = all;
+        }
+
+        Ok(())
+    }
+
+    fn setup(&mut self) -> Result<()> {
+        let data_raw: Vec<i128> = self
+            .input
+            .lines()
+            .enumerate()
+            .map(|(i, s)| {
+                s.parse::<i64>()
+                    .map(i128::from)
+                    .map_err(|e| Error::parse(i + 1, 1, format!("\"{}\": {}", s, e)))
+            })
+            .collect::<Result<Vec<i128>>>()?
+            .into_iter()
+            .sorted()
+            .collect();
+        let smallest = data_raw.first().cloned().unwrap_or(0);
+        // Every other entry in a tuple is at least the smallest, so nothing larger can be part of
+        // one that sums to the target. Part 2's extra entry loosens the bound if that's negative.
+        // A bound too large to compute can't rule anything out.
+        let others = if smallest < 0 { self.k } else { self.k - 1 };
+        let largest = i128::try_from(others)
+            .ok()
+            .and_then(|others| smallest.checked_mul(others))
+            .and_then(|lowest| self.target.checked_sub(lowest));
+
+        self.data = Some(match largest {
+            Some(largest) => data_raw.into_iter().filter(|&x| x <= largest).collect(),
+            None => data_raw,
+        });
+
+        Ok(())
+    }
+
+    fn parsed(&self) -> &dyn Debug {
+        &self.data
+    }
+
+    fn part_1(&mut self) -> Result<Answer> {
+        let data = self.data.as_ref().ok_or(Error::SetupRequired)?;
+        self.solve(data, self.k)
+    }
+
+    fn part_2(&mut self) -> Result<Answer> {
+        let data = self.data.as_ref().ok_or(Error::SetupRequired)?;
+        self.solve(data, self.k + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::y2020::{run_example, run_example_with};
+
+    const EXAMPLE: &str = "1721\n979\n366\n299\n675\n1456\n";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            run_example(1, EXAMPLE).unwrap(),
+            (Answer::from(514579), Answer::from(241861950))
+        );
+    }
+
+    #[test]
+    fn configured_target_and_k() {
+        let mut params = Params::new();
+        params.insert("target", "26");
+        params.insert("k", "3");
+
+        assert_eq!(
+            run_example_with(1, "2\n3\n5\n7\n11\n13\n", &params).unwrap(),
+            (Answer::from(2 * 11 * 13), Answer::from(3 * 5 * 7 * 11))
+        );
+    }
+
+    #[test]
+    fn enumerate_every_tuple() {
+        let mut params = Params::new();
+        params.insert("target", "10");
+        params.insert("all", "true");
+
+        assert_eq!(
+            run_example_with(1, "1\n5\n9\n5\n3\n2\n", &params).unwrap(),
+            (
+                Answer::List(vec![
+                    Answer::from(2),
+                    Answer::List(vec![Answer::from(vec![1, 9]), Answer::from(9)]),
+                    Answer::List(vec![Answer::from(vec![5, 5]), Answer::from(25)]),
+                ]),
+                Answer::List(vec![
+                    Answer::from(1),
+                    Answer::List(vec![Answer::from(vec![2, 3, 5]), Answer::from(30)]),
+                ]),
+            )
+        );
+    }
+
+    #[test]
+    fn negative_and_64_bit_entries() {
+        let mut params = Params::new();
+        params.insert("target", "7");
+        params.insert("k", "1");
+        assert_eq!(
+            run_example_with(1, "-5\n7\n12\n", &params).unwrap(),
+            (Answer::from(7), Answer::from(-60))
+        );
+
+        params.insert("target", "9000000000000000000");
+        assert_eq!(
+            run_example_with(
+                1,
+                "9000000000000000000\n4000000000000000000\n5000000000000000000\n",
+                &params
+            )
+            .unwrap(),
+            (
+                Answer::from(9_000_000_000_000_000_000i64),
+                Answer::from(20_000_000_000_000_000_000_000_000_000_000_000_000i128)
+            )
+        );
+    }
+
+    #[test]
+    fn product_overflow() {
+        let mut params = Params::new();
+        params.insert("target", "9000000000000000000");
+        params.insert("k", "3");
+
+        assert!(matches!(
+            run_example_with(
+                1,
+                "3000000000000000000\n3000000000000000000\n3000000000000000000\n",
+                &params
+            ),
+            Err(Error::Overflow(_))
+        ));
+    }
+
+    #[test]
+    fn reject_out_of_range_k() {
+        let mut params = Params::new();
+        for k in &["0", "18446744073709551615"] {
+            params.insert("k", *k);
+            assert!(matches!(
+                run_example_with(1, EXAMPLE, &params),
+                Err(Error::Param(_))
+            ));
+        }
+
+        params.insert("k", "4000000000");
+        assert!(matches!(
+            run_example_with(1, EXAMPLE, &params),
+            Err(Error::NoSolution(_))
+        ));
+    }
+
+    #[test]
+    fn reject_unknown_params() {
+        let mut params = Params::new();
+        params.insert("size", "3");
+
+        assert!(matches!(
+            run_example_with(1, EXAMPLE, &params),
+            Err(Error::Param(_))
+        ));
+    }
+
+    #[test]
+    fn no_entries_sum_to_2020() {
+        assert!(matches!(
+            run_example(1, "1\n2\n3\n"),
+            Err(Error::NoSolution(_))
+        ));
+
+        let mut params = Params::new();
+        params.insert("all", "true");
+        assert!(matches!(
+            run_example_with(1, "1\n2\n3\n", &params),
+            Err(Error::NoSolution(_))
+        ));
+    }
+
+    #[test]
+    fn invalid_entry() {
+        assert!(matches!(
+            run_example(1, "1721\nabc\n"),
+            Err(Error::Parse { line: 2, .. })
+        ));
+    }
+}
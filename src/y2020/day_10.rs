@@ -0,0 +1,193 @@
+//! Day 10: Adapter Array
+
+use itertools::Itertools;
+use petgraph::visit::Bfs;
+use petgraph::Directed;
+use petgraph::Incoming;
+use petgraph::{graph::NodeIndex, Graph};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt::Debug;
+use std::iter;
+
+use crate::days::{Answer, Challenge, Registration};
+use crate::error::{Error, Result};
+
+/// Chains the joltage adapters, sorted with the outlet (0) and device (max + 3) at either end
+pub struct Day10<'a> {
+    data: Option<Vec<u32>>,
+    input: &'a str,
+}
+
+impl<'a> Day10<'a> {
+    pub fn build(input: &'a str) -> Box<Day10<'a>> {
+        Box::new(Day10 { data: None, input })
+    }
+}
+
+pub const REGISTRATION: Registration = Registration {
+    day: 10,
+    title: "Adapter Array",
+    build: |input| Day10::build(input),
+};
+
+/// The product of the counts of each joltage difference between consecutive adapters, which
+/// must be sorted and include the outlet and device, or `None` if it overflows
+pub fn joltage_distribution(adapters: &[u32]) -> Option<u32> {
+    let count_map: HashMap<u32, u32> = HashMap::new();
+
+    adapters
+        .iter()
+        .tuple_windows::<(&u32, &u32)>()
+        .fold(count_map, |mut acc, (f, l)| {
+            let counter = acc.entry(l - f).or_insert(0);
+            *counter += 1;
+
+            acc
+        })
+        .values()
+        .try_fold(1u32, |acc, &count| acc.checked_mul(count))
+}
+
+/// The number of distinct chains of sorted adapters from the outlet to the device, counted by
+/// walking a DAG of compatible adapters from the device
+pub fn count_arrangements(adapters: &[u32]) -> Result<u128> {
+    let mut idx_vert_map: HashMap<u32, NodeIndex<u32>> = HashMap::new();
+    let mut dag: Graph<String, String, Directed> =
+        Graph::with_capacity(adapters.len(), adapters.len() * 3);
+    for n in adapters.iter().rev() {
+        let idx = dag.add_node(n.to_string());
+        idx_vert_map.insert(*n, idx);
+    }
+
+    let mut path_counts: HashMap<NodeIndex, u128> =
+        idx_vert_map.values().map(|v| (v.to_owned(), 1)).collect();
+    let (src, snk) = match (adapters.last(), adapters.first()) {
+        (Some(last), Some(first)) => (idx_vert_map[last], idx_vert_map[first]),
+        _ => return Err(Error::no_solution("there are no adapters")),
+    };
+
+    let edges: Vec<(NodeIndex<u32>, NodeIndex<u32>, String)> = adapters
+        .iter()
+        .rev()
+        .flat_map(|&n| {
+            let valid_parents: Vec<u32> = adapters
+                .iter()
+                .rev()
+                .filter(|&&v| v < n && n - v <= 3)
+                .cloned()
+                .collect();
+
+            iter::repeat(n)
+                .zip(valid_parents)
+                .collect::<Vec<(u32, u32)>>()
+        })
+        .sorted()
+        .map(|(s, e)| (idx_vert_map[&s], idx_vert_map[&e], format!("{}-{}", s, e)))
+        .collect();
+    dag.extend_with_edges(edges);
+
+    let mut bfs = Bfs::new(&dag, src);
+    while let Some(nx) = bfs.next(&dag) {
+        let mut neighbours = 1;
+        if nx != src {
+            neighbours = dag
+                .neighbors_directed(nx, Incoming)
+                .try_fold(0u128, |acc, f| acc.checked_add(path_counts[&f]))
+                .ok_or_else(|| Error::Overflow("too many arrangements to count".to_string()))?;
+        }
+        path_counts.insert(nx, neighbours);
+    }
+
+    Ok(path_counts[&snk])
+}
+
+impl Challenge<'_> for Day10<'_> {
+    fn setup(&mut self) -> Result<()> {
+        let raw_str = self.input;
+
+        let mut data: Vec<u32> = iter::once(Ok(0))
+            .chain(raw_str.lines().enumerate().map(|(i, s)| {
+                s.parse::<u32>()
+                    .map_err(|e| Error::parse(i + 1, 1, format!("\"{}\": {}", s, e)))
+            }))
+            .collect::<Result<Vec<u32>>>()?
+            .into_iter()
+            .sorted()
+            .collect();
+
+        let highest = data.last().cloned().unwrap_or(0);
+        let device = highest.checked_add(3).ok_or_else(|| {
+            Error::Overflow(format!("the device is rated 3 jolts above {}", highest))
+        })?;
+        data.push(device);
+        self.data = Some(data);
+
+        Ok(())
+    }
+
+    fn parsed(&self) -> &dyn Debug {
+        &self.data
+    }
+
+    fn part_1(&mut self) -> Result<Answer> {
+        let data = self.data.as_ref().ok_or(Error::SetupRequired)?;
+        joltage_distribution(data).map(Answer::from).ok_or_else(|| {
+            Error::Overflow("the product of the difference counts is too large".to_string())
+        })
+    }
+
+    fn part_2(&mut self) -> Result<Answer> {
+        let data = self.data.as_ref().ok_or(Error::SetupRequired)?;
+        let arrangements = count_arrangements(data)?;
+        i128::try_from(arrangements)
+            .map(Answer::from)
+            .map_err(|_| Error::Overflow(format!("{} arrangements", arrangements)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days::Answer;
+    use crate::error::Error;
+    use crate::y2020::run_example;
+
+    const SMALL_EXAMPLE: &str = "16\n10\n15\n5\n1\n11\n7\n19\n6\n12\n4\n";
+
+    const LARGE_EXAMPLE: &str = "28\n33\n18\n42\n31\n14\n46\n20\n48\n47\n24\n23\n49\n45\n19\n38\n\
+                                 39\n11\n1\n32\n25\n35\n8\n17\n7\n9\n4\n2\n34\n10\n3\n";
+
+    #[test]
+    fn small_example() {
+        assert_eq!(
+            run_example(10, SMALL_EXAMPLE).unwrap(),
+            (Answer::from(35), Answer::from(8))
+        );
+    }
+
+    #[test]
+    fn large_example() {
+        assert_eq!(
+            run_example(10, LARGE_EXAMPLE).unwrap(),
+            (Answer::from(220), Answer::from(19208))
+        );
+    }
+
+    #[test]
+    fn too_many_arrangements() {
+        let adapters: String = (1..=200).map(|n| format!("{}\n", n)).collect();
+
+        assert!(matches!(
+            run_example(10, &adapters),
+            Err(Error::Overflow(_))
+        ));
+    }
+
+    #[test]
+    fn device_rating_overflow() {
+        assert!(matches!(
+            run_example(10, "1\n4294967295\n"),
+            Err(Error::Overflow(_))
+        ));
+    }
+}
@@ -0,0 +1,630 @@
+//! Day 2: Password Philosophy
+
+use crate::days::{Answer, Challenge, Params, Registration};
+use crate::error::{Error, Result};
+use regex::Regex;
+use std::fmt::{self, Debug};
+use std::iter::Peekable;
+use std::str::{Chars, FromStr};
+
+/// A line of the password database, such as `1-3 a: abcde`: the policy's two numbers, its
+/// letter and the password
+#[derive(Debug, Clone, PartialEq)]
+pub struct PasswordDetails {
+    pub first: usize,
+    pub second: usize,
+    pub letter: char,
+    pub password: String,
+}
+
+/// Why a line of the password database couldn't be parsed: the 1-indexed column where the
+/// parser expected one token but found something else
+#[derive(Debug, Clone, PartialEq)]
+pub struct PasswordParseError {
+    pub column: usize,
+    pub expected: &'static str,
+    pub found: String,
+}
+
+impl fmt::Display for PasswordParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected {} but found {}", self.expected, self.found)
+    }
+}
+
+/// Reads a line a token at a time, tracking the column for errors
+struct LineParser<'a> {
+    chars: Peekable<Chars<'a>>,
+    column: usize,
+}
+
+impl<'a> LineParser<'a> {
+    fn new(line: &'a str) -> Self {
+        LineParser {
+            chars: line.chars().peekable(),
+            column: 1,
+        }
+    }
+
+    fn error(&mut self, expected: &'static str) -> PasswordParseError {
+        PasswordParseError {
+            column: self.column,
+            expected,
+            found: self
+                .chars
+                .peek()
+                .map_or_else(|| "the end of the line".to_string(), |c| format!("{:?}", c)),
+        }
+    }
+
+    fn advance(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        self.column += 1;
+
+        Some(c)
+    }
+
+    /// Consume characters while they satisfy the predicate
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> String {
+        let mut taken = String::new();
+        while let Some(&c) = self.chars.peek() {
+            if !predicate(c) {
+                break;
+            }
+            taken.push(c);
+            self.advance();
+        }
+
+        taken
+    }
+
+    fn expect_char(&mut self, expected_char: char, expected: &'static str) -> ParseResult<()> {
+        match self.chars.peek() {
+            Some(&c) if c == expected_char => {
+                self.advance();
+                Ok(())
+            }
+            _ => Err(self.error(expected)),
+        }
+    }
+
+    fn expect_whitespace(&mut self) -> ParseResult<()> {
+        if self.take_while(char::is_whitespace).is_empty() {
+            Err(self.error("a space"))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// A positive number, reporting the column it started at if it's out of range
+    fn expect_number(&mut self) -> ParseResult<usize> {
+        let column = self.column;
+        let digits = self.take_while(|c| c.is_ascii_digit());
+        if digits.is_empty() {
+            return Err(self.error("a number"));
+        }
+
+        digits
+            .parse()
+            .ok()
+            .filter(|&n| n > 0)
+            .ok_or(PasswordParseError {
+                column,
+                expected: "a number from 1",
+                found: digits,
+            })
+    }
+
+    fn expect_letter(&mut self) -> ParseResult<char> {
+        match self.chars.peek() {
+            Some(&c) if !c.is_whitespace() && c != ':' => {
+                self.advance();
+                Ok(c)
+            }
+            _ => Err(self.error("a letter")),
+        }
+    }
+
+    fn expect_password(&mut self) -> ParseResult<String> {
+        let password = self.take_while(|c| !c.is_whitespace());
+        if password.is_empty() {
+            Err(self.error("a password"))
+        } else {
+            Ok(password)
+        }
+    }
+
+    fn expect_end(&mut self) -> ParseResult<()> {
+        self.take_while(char::is_whitespace);
+        match self.chars.peek() {
+            None => Ok(()),
+            Some(_) => Err(self.error("the end of the line")),
+        }
+    }
+}
+
+type ParseResult<T> = std::result::Result<T, PasswordParseError>;
+
+impl FromStr for PasswordDetails {
+    type Err = PasswordParseError;
+
+    /// Parse a line of the form `FIRST-SECOND LETTER: PASSWORD`, where the numbers are positive
+    /// and any run of whitespace may separate the tokens
+    fn from_str(s: &str) -> ParseResult<Self> {
+        let mut parser = LineParser::new(s);
+
+        parser.take_while(char::is_whitespace);
+        let first = parser.expect_number()?;
+        parser.expect_char('-', "'-'")?;
+        let second = parser.expect_number()?;
+        parser.expect_whitespace()?;
+        let letter = parser.expect_letter()?;
+        parser.expect_char(':', "':'")?;
+        parser.expect_whitespace()?;
+        let password = parser.expect_password()?;
+        parser.expect_end()?;
+
+        Ok(PasswordDetails {
+            first,
+            second,
+            letter,
+            password,
+        })
+    }
+}
+
+/// A rule a password must follow
+pub trait PasswordPolicy: Debug + Send {
+    /// The name the policy is selected by, as used in reports
+    fn name(&self) -> &'static str;
+    /// Why the line's password breaks the policy, or `None` if it follows it
+    fn violation(&self, details: &PasswordDetails) -> Option<String>;
+}
+
+/// The sled rental policy: the letter appears between the first and second number of times
+#[derive(Debug)]
+pub struct CountInRange;
+
+impl PasswordPolicy for CountInRange {
+    fn name(&self) -> &'static str {
+        "count"
+    }
+
+    fn violation(&self, d: &PasswordDetails) -> Option<String> {
+        let count = d.password.matches(d.letter).count();
+        if (d.first..=d.second).contains(&count) {
+            None
+        } else {
+            Some(format!(
+                "'{}' appears {} times, not {}-{}",
+                d.letter, count, d.first, d.second
+            ))
+        }
+    }
+}
+
+/// The toboggan policy: the letter is at exactly one of the two 1-indexed positions
+#[derive(Debug)]
+pub struct ExactlyOnePosition;
+
+impl PasswordPolicy for ExactlyOnePosition {
+    fn name(&self) -> &'static str {
+        "position"
+    }
+
+    fn violation(&self, d: &PasswordDetails) -> Option<String> {
+        let at = |position: usize| d.password.chars().nth(position - 1) == Some(d.letter);
+
+        match (at(d.first), at(d.second)) {
+            (true, false) | (false, true) => None,
+            (true, true) => Some(format!(
+                "'{}' is at both positions {} and {}",
+                d.letter, d.first, d.second
+            )),
+            (false, false) => Some(format!(
+                "'{}' is at neither position {} nor {}",
+                d.letter, d.first, d.second
+            )),
+        }
+    }
+}
+
+/// The password has at least `min` and at most `max` characters
+#[derive(Debug)]
+pub struct LengthRange {
+    pub min: Option<usize>,
+    pub max: Option<usize>,
+}
+
+impl PasswordPolicy for LengthRange {
+    fn name(&self) -> &'static str {
+        "length"
+    }
+
+    fn violation(&self, d: &PasswordDetails) -> Option<String> {
+        let length = d.password.chars().count();
+        match (self.min, self.max) {
+            (Some(min), _) if length < min => Some(format!("{} characters, under {}", length, min)),
+            (_, Some(max)) if length > max => Some(format!("{} characters, over {}", length, max)),
+            _ => None,
+        }
+    }
+}
+
+/// The password contains none of the characters
+#[derive(Debug)]
+pub struct ForbiddenChars(pub String);
+
+impl PasswordPolicy for ForbiddenChars {
+    fn name(&self) -> &'static str {
+        "forbid"
+    }
+
+    fn violation(&self, d: &PasswordDetails) -> Option<String> {
+        d.password
+            .chars()
+            .find(|c| self.0.contains(*c))
+            .map(|c| format!("contains '{}'", c))
+    }
+}
+
+/// The password matches the regular expression
+#[derive(Debug)]
+pub struct MatchesRegex(pub Regex);
+
+impl PasswordPolicy for MatchesRegex {
+    fn name(&self) -> &'static str {
+        "regex"
+    }
+
+    fn violation(&self, d: &PasswordDetails) -> Option<String> {
+        if self.0.is_match(&d.password) {
+            None
+        } else {
+            Some(format!("doesn't match /{}/", self.0.as_str()))
+        }
+    }
+}
+
+fn optional_bound(spec: &str, bound: &str) -> Result<Option<usize>> {
+    if bound.is_empty() {
+        return Ok(None);
+    }
+
+    bound
+        .parse()
+        .map(Some)
+        .map_err(|e| Error::Param(format!("\"{}\": {}", spec, e)))
+}
+
+/// Parse a policy named by `count`, `position`, `length:MIN-MAX` (either bound may be left out),
+/// `forbid:CHARS` or `regex:PATTERN`
+pub fn parse_policy(spec: &str) -> Result<Box<dyn PasswordPolicy>> {
+    let (name, arg) = match spec.split_once(':') {
+        Some((name, arg)) => (name, Some(arg)),
+        None => (spec, None),
+    };
+
+    match (name, arg) {
+        ("count", None) => Ok(Box::new(CountInRange)),
+        ("position", None) => Ok(Box::new(ExactlyOnePosition)),
+        ("length", Some(range)) => {
+            let (min, max) = range
+                .split_once('-')
+                .ok_or_else(|| Error::Param(format!("\"{}\" needs a MIN-MAX range", spec)))?;
+            Ok(Box::new(LengthRange {
+                min: optional_bound(spec, min)?,
+                max: optional_bound(spec, max)?,
+            }))
+        }
+        ("forbid", Some(chars)) => Ok(Box::new(ForbiddenChars(chars.to_string()))),
+        ("regex", Some(pattern)) => Regex::new(pattern)
+            .map(|re| Box::new(MatchesRegex(re)) as Box<dyn PasswordPolicy>)
+            .map_err(|e| Error::Param(format!("\"{}\": {}", spec, e))),
+        _ => Err(Error::Param(format!(
+            "Unknown password policy \"{}\"",
+            spec
+        ))),
+    }
+}
+
+/// Parse policies separated by `;`, all of which a password must follow
+pub fn parse_policies(specs: &str) -> Result<Vec<Box<dyn PasswordPolicy>>> {
+    specs.split(';').map(parse_policy).collect()
+}
+
+/// Why the password breaks each policy it doesn't follow
+pub fn violations(details: &PasswordDetails, policies: &[Box<dyn PasswordPolicy>]) -> Vec<String> {
+    policies
+        .iter()
+        .filter_map(|p| p.violation(details).map(|v| format!("{}: {}", p.name(), v)))
+        .collect()
+}
+
+/// Count the passwords that follow every policy
+pub fn validate_passwords(
+    passwords: &[(usize, PasswordDetails)],
+    policies: &[Box<dyn PasswordPolicy>],
+) -> usize {
+    passwords
+        .iter()
+        .filter(|(_, d)| violations(d, policies).is_empty())
+        .count()
+}
+
+/// A line for each password that breaks a policy, naming its line of the input, the policies it
+/// broke and why
+pub fn violation_report(
+    passwords: &[(usize, PasswordDetails)],
+    policies: &[Box<dyn PasswordPolicy>],
+) -> Vec<String> {
+    passwords
+        .iter()
+        .filter_map(|(line, d)| {
+            let broken = violations(d, policies);
+            if broken.is_empty() {
+                None
+            } else {
+                Some(format!("line {}: {}", line, broken.join("; ")))
+            }
+        })
+        .collect()
+}
+
+/// Counts the passwords in the database that follow the sled rental policy in part 1 and the
+/// toboggan policy in part 2. The `policy` parameter replaces part 1's policy with any set of
+/// policies, and `report` lists each line's violations instead of counting the valid lines.
+/// With `lenient`, malformed lines are skipped and reported rather than failing the setup.
+pub struct Day2<'a> {
+    /// Each parsed password with its 1-indexed line of the input, which skipped lines leave gaps
+    /// in
+    data: Option<Vec<(usize, PasswordDetails)>>,
+    input: &'a str,
+    policies: Vec<Box<dyn PasswordPolicy>>,
+    report: bool,
+    lenient: bool,
+    skipped: Vec<String>,
+}
+
+impl<'a> Day2<'a> {
+    pub fn build(input: &'a str) -> Box<Day2<'a>> {
+        Box::new(Day2 {
+            data: None,
+            input,
+            policies: vec![Box::new(CountInRange)],
+            report: false,
+            lenient: false,
+            skipped: Vec::new(),
+        })
+    }
+
+    fn solve(&self, policies: &[Box<dyn PasswordPolicy>]) -> Result<Answer> {
+        let data = self.data.as_ref().ok_or(Error::SetupRequired)?;
+
+        if self.report {
+            Ok(Answer::from(violation_report(data, policies)))
+        } else {
+            Ok(Answer::from(validate_passwords(data, policies)))
+        }
+    }
+}
+
+pub const REGISTRATION: Registration = Registration {
+    day: 2,
+    title: "Password Philosophy",
+    build: |input| Day2::build(input),
+};
+
+impl Challenge<'_> for Day2<'_> {
+    fn configure(&mut self, params: &Params) -> Result<()> {
+        params.expect_only(&["policy", "report", "lenient"])?;
+
+        if let Some(specs) = params.get::<String>("policy")? {
+            self.policies = parse_policies(&specs)?;
+        }
+        if let Some(report) = params.get("report")? {
+            self.report = report;
+        }
+        if let Some(lenient) = params.get("lenient")? {
+            self.lenient = lenient;
+        }
+
+        Ok(())
+    }
+
+    fn setup(&mut self) -> Result<()> {
+        let mut data = Vec::new();
+        self.skipped.clear();
+
+        for (i, line) in self.input.lines().enumerate() {
+            match PasswordDetails::from_str(line) {
+                Ok(details) => data.push((i + 1, details)),
+                Err(e) => {
+                    let err = Error::parse(i + 1, e.column, e.to_string());
+                    if !self.lenient {
+                        return Err(err);
+                    }
+                    self.skipped.push(err.to_string());
+                }
+            }
+        }
+        self.data = Some(data);
+
+        Ok(())
+    }
+
+    fn parsed(&self) -> &dyn Debug {
+        &self.data
+    }
+
+    fn warnings(&self) -> Vec<String> {
+        self.skipped
+            .iter()
+            .map(|err| format!("Skipped a line: {}", err))
+            .collect()
+    }
+
+    fn part_1(&mut self) -> Result<Answer> {
+        self.solve(&self.policies)
+    }
+
+    fn part_2(&mut self) -> Result<Answer> {
+        self.solve(&[Box::new(ExactlyOnePosition)])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::configured_challenge;
+    use crate::y2020::{run_example, run_example_with};
+
+    const EXAMPLE: &str = "1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc\n";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            run_example(2, EXAMPLE).unwrap(),
+            (Answer::from(2), Answer::from(1))
+        );
+    }
+
+    #[test]
+    fn selected_policies() {
+        let mut params = Params::new();
+        params.insert("policy", "length:6-;forbid:xyz");
+        assert_eq!(
+            run_example_with(2, "1-3 a: abcdef\n1-3 b: abcxyz\n1-3 c: abc\n", &params).unwrap(),
+            (Answer::from(1), Answer::from(2))
+        );
+
+        params.insert("policy", "regex:^c+$");
+        assert_eq!(
+            run_example_with(2, EXAMPLE, &params).unwrap().0,
+            Answer::from(1)
+        );
+
+        params.insert("policy", "length:6");
+        assert!(matches!(
+            run_example_with(2, EXAMPLE, &params),
+            Err(Error::Param(_))
+        ));
+    }
+
+    #[test]
+    fn report_violations() {
+        let mut params = Params::new();
+        params.insert("policy", "count;length:-5");
+        params.insert("report", "true");
+
+        assert_eq!(
+            run_example_with(2, EXAMPLE, &params).unwrap(),
+            (
+                Answer::from(vec![
+                    "line 2: count: 'b' appears 0 times, not 1-3",
+                    "line 3: length: 9 characters, over 5",
+                ]),
+                Answer::from(vec![
+                    "line 2: position: 'b' is at neither position 1 nor 3",
+                    "line 3: position: 'c' is at both positions 2 and 9",
+                ])
+            )
+        );
+    }
+
+    #[test]
+    fn malformed_policy() {
+        assert!(matches!(
+            run_example(2, "1-3 a: abcde\n1 a: abcde\n"),
+            Err(Error::Parse { line: 2, .. })
+        ));
+    }
+
+    fn parse_error(line: &str) -> (usize, &'static str, String) {
+        let e = PasswordDetails::from_str(line).unwrap_err();
+        (e.column, e.expected, e.found)
+    }
+
+    #[test]
+    fn locate_parse_errors() {
+        assert_eq!(
+            PasswordDetails::from_str("  1-3  a:   abcde ").unwrap(),
+            PasswordDetails {
+                first: 1,
+                second: 3,
+                letter: 'a',
+                password: "abcde".to_string()
+            }
+        );
+
+        assert_eq!(parse_error("1 a: abcde"), (2, "'-'", "' '".to_string()));
+        assert_eq!(
+            parse_error("1-x a: abcde"),
+            (3, "a number", "'x'".to_string())
+        );
+        assert_eq!(
+            parse_error("0-3 a: abcde"),
+            (1, "a number from 1", "0".to_string())
+        );
+        assert_eq!(parse_error("1-3 ab: abcde"), (6, "':'", "'b'".to_string()));
+        assert_eq!(
+            parse_error("1-3 a:"),
+            (7, "a space", "the end of the line".to_string())
+        );
+        assert_eq!(
+            parse_error("1-3 a: abc de"),
+            (12, "the end of the line", "'d'".to_string())
+        );
+        assert_eq!(
+            parse_error("99999999999999999999-3 a: abcde"),
+            (1, "a number from 1", "99999999999999999999".to_string())
+        );
+    }
+
+    #[test]
+    fn lenient_setup_skips_bad_lines() {
+        let input = "1-3 a: abcde\n1-3 b cdefg\n2-9 c: ccccccccc\n";
+        assert!(matches!(
+            run_example(2, input),
+            Err(Error::Parse {
+                line: 2,
+                column: 6,
+                ..
+            })
+        ));
+
+        let mut params = Params::new();
+        params.insert("lenient", "true");
+        let mut day = configured_challenge(2020, 2, input, &params).unwrap();
+        day.setup().unwrap();
+
+        assert_eq!(day.part_1().unwrap(), Answer::from(2));
+        assert_eq!(
+            day.warnings(),
+            vec!["Skipped a line: Parse error at line 2, column 6: expected ':' but found ' '"]
+        );
+    }
+
+    #[test]
+    fn report_input_line_numbers_around_skipped_lines() {
+        let mut params = Params::new();
+        params.insert("lenient", "true");
+        params.insert("report", "true");
+
+        assert_eq!(
+            run_example_with(
+                2,
+                "1-3 a: abcde\nbad line\n1-3 b: cdefg\n2-9 c: ccccccccc\n",
+                &params
+            )
+            .unwrap(),
+            (
+                Answer::from(vec!["line 3: count: 'b' appears 0 times, not 1-3"]),
+                Answer::from(vec![
+                    "line 3: position: 'b' is at neither position 1 nor 3",
+                    "line 4: position: 'c' is at both positions 2 and 9",
+                ])
+            )
+        );
+    }
+}
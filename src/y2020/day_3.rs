@@ -0,0 +1,182 @@
+//! Day 3: Toboggan Trajectory
+
+use std::fmt::Debug;
+
+use crate::days::{Answer, Challenge, Registration};
+use crate::error::{Error, Result};
+
+/// A slope as (right, down) steps
+pub type Gradient = (usize, usize);
+
+#[derive(Debug, Clone)]
+pub enum MapElement {
+    Clear,
+    Tree,
+}
+
+/// Counts the trees hit while sledding down the map at various gradients
+pub struct Day3<'a> {
+    data: Option<SledMap>,
+    input: &'a str,
+}
+
+impl<'a> Day3<'a> {
+    pub fn build(input: &'a str) -> Box<Day3<'a>> {
+        Box::new(Day3 { data: None, input })
+    }
+}
+
+pub const REGISTRATION: Registration = Registration {
+    day: 3,
+    title: "Toboggan Trajectory",
+    build: |input| Day3::build(input),
+};
+
+/// The map of open squares and trees, with each row repeated to the right so every gradient
+/// can reach the bottom
+#[derive(Debug)]
+pub struct SledMap {
+    size: (usize, usize),
+    elements: Vec<Vec<MapElement>>,
+}
+
+impl MapElement {
+    pub fn from_char(s: &char) -> std::result::Result<Self, String> {
+        match s {
+            '#' => Ok(MapElement::Tree),
+            '.' => Ok(MapElement::Clear),
+            x => Err(format!("Unsupported map element found: {}", x)),
+        }
+    }
+
+    pub fn is_tree(&self) -> bool {
+        matches!(self, MapElement::Tree)
+    }
+}
+
+impl SledMap {
+    /// Parse the map's rows, repeating each row to `repeat_x` times the map's height
+    pub fn build(input: &[&str], repeat_x: usize) -> Result<SledMap> {
+        let width = input.first().map_or(0, |s| s.len());
+        if width == 0 {
+            return Err(Error::parse(1, 1, "The map is empty"));
+        }
+
+        let dims = (input.len(), width);
+        let data: Vec<Vec<MapElement>> = input
+            .iter()
+            .enumerate()
+            .map(|(i, s)| {
+                let row = s
+                    .chars()
+                    .enumerate()
+                    .map(|(j, c)| {
+                        MapElement::from_char(&c).map_err(|e| Error::parse(i + 1, j + 1, e))
+                    })
+                    .collect::<Result<Vec<MapElement>>>()?;
+                if row.len() != width {
+                    return Err(Error::parse(
+                        i + 1,
+                        row.len() + 1,
+                        format!("Expected a row of width {}", width),
+                    ));
+                }
+
+                Ok(row
+                    .into_iter()
+                    .cycle()
+                    .take(dims.0 * repeat_x) // 3x wider than it is tall
+                    .collect())
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(SledMap {
+            size: dims,
+            elements: data,
+        })
+    }
+
+    pub fn get_coord(&self, x: usize, y: usize) -> Option<&MapElement> {
+        self.elements.get(y).and_then(|r| r.get(x))
+    }
+}
+
+/// The number of trees hit from the top left to the bottom of the map at the gradient. Every row
+/// the sled lands on counts, including the last when the height isn't a multiple of the step
+/// down.
+pub fn count_trees(sled_map: &SledMap, (x, y): Gradient) -> usize {
+    (0..sled_map.size.0.div_ceil(y))
+        .filter(|i| {
+            sled_map
+                .get_coord(i * x, i * y)
+                .is_some_and(MapElement::is_tree)
+        })
+        .count()
+}
+
+impl Challenge<'_> for Day3<'_> {
+    fn setup(&mut self) -> Result<()> {
+        let raw_data = self.input;
+        let lines: Vec<&str> = raw_data.lines().collect();
+
+        self.data = Some(SledMap::build(&lines, 7)?);
+
+        Ok(())
+    }
+
+    fn parsed(&self) -> &dyn Debug {
+        &self.data
+    }
+
+    fn part_1(&mut self) -> Result<Answer> {
+        let sled_map = self.data.as_ref().ok_or(Error::SetupRequired)?;
+
+        Ok(Answer::from(count_trees(sled_map, (3, 1))))
+    }
+
+    fn part_2(&mut self) -> Result<Answer> {
+        let sled_map = self.data.as_ref().ok_or(Error::SetupRequired)?;
+        let gradients: Vec<Gradient> = vec![(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+
+        Ok(Answer::from(
+            gradients
+                .iter()
+                .map(|(x, y)| count_trees(sled_map, (*x, *y)))
+                .fold(1, |x, y| x * y as i64),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::y2020::run_example;
+
+    const EXAMPLE: &str = "..##.......
+#...#...#..
+.#....#..#.
+..#.#...#.#
+.#...##..#.
+..#.##.....
+.#.#.#....#
+.#........#
+#.##...#...
+#...##....#
+.#..#...#.#
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            run_example(3, EXAMPLE).unwrap(),
+            (Answer::from(7), Answer::from(336))
+        );
+    }
+
+    #[test]
+    fn steep_gradient_reaches_the_last_row() {
+        let sled_map = SledMap::build(&["...", "...", "#.."], 7).unwrap();
+
+        assert_eq!(count_trees(&sled_map, (0, 2)), 1);
+    }
+}
@@ -1,9 +1,13 @@
-use crate::days::Challenge;
+//! Day 4: Passport Processing
+
+use crate::days::{Answer, Challenge, Registration};
+use crate::error::{Error, Result};
 use regex::Regex;
-use std::{fs, path::Path, str::FromStr};
+use std::fmt::Debug;
+use std::str::FromStr;
 
 #[derive(Debug)]
-enum EyeColour {
+pub enum EyeColour {
     Amber,
     Blue,
     Brown,
@@ -16,7 +20,7 @@ enum EyeColour {
 impl FromStr for EyeColour {
     type Err = String;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
         match s {
             "amb" => Ok(EyeColour::Amber),
             "blu" => Ok(EyeColour::Blue),
@@ -30,32 +34,34 @@ impl FromStr for EyeColour {
     }
 }
 
+/// Counts the passports with all required fields, then those whose fields are also valid
 pub struct Day4<'a> {
     data: Option<Vec<Passport>>,
-    file_path: &'a Path,
-    part_1_ans: Option<usize>,
-    part_2_ans: Option<usize>,
+    input: &'a str,
 }
 
-impl<'a> Day4<'_> {
-    pub fn build(file_path: &'a Path) -> Box<Day4> {
-        Box::new(Day4 {
-            data: None,
-            part_1_ans: None,
-            part_2_ans: None,
-            file_path: file_path,
-        })
+impl<'a> Day4<'a> {
+    pub fn build(input: &'a str) -> Box<Day4<'a>> {
+        Box::new(Day4 { data: None, input })
     }
 }
 
+pub const REGISTRATION: Registration = Registration {
+    day: 4,
+    title: "Passport Processing",
+    build: |input| Day4::build(input),
+};
+
+/// A height with its unit, parsed from strings like `183cm` or `59in`
 #[derive(Debug)]
-struct Height {
+pub struct Height {
     size: u32,
     unit: String,
 }
 
 impl Height {
-    fn is_valid(&self) -> bool {
+    /// Whether the height is 150-193cm or 59-76in
+    pub fn is_valid(&self) -> bool {
         let u = self.unit.as_str();
 
         match u {
@@ -69,13 +75,13 @@ impl Height {
 impl FromStr for Height {
     type Err = String;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
         lazy_static! {
             static ref RE: Regex = Regex::new(r"(?P<size>[0-9]+)(?P<unit>(cm|in))").unwrap();
         }
 
         RE.captures(s)
-            .map_or_else(|| None, |c| Some(c))
+            .map_or_else(|| None, Some)
             .map_or_else(
                 || None,
                 |c| {
@@ -85,7 +91,7 @@ impl FromStr for Height {
                     ))
                 },
             )
-            .map_or_else(|| None, |(s, u)| Some((u32::from_str(s).unwrap(), u)))
+            .and_then(|(s, u)| u32::from_str(s).ok().map(|s| (s, u)))
             .map_or_else(
                 || Err("Failed to parse height".to_string()),
                 |(size, unit)| {
@@ -98,6 +104,7 @@ impl FromStr for Height {
     }
 }
 
+/// The raw fields of a passport, any of which may be missing
 #[derive(Debug)]
 pub struct Passport {
     byr: Option<u32>,
@@ -109,6 +116,7 @@ pub struct Passport {
     pid: Option<String>,
 }
 
+/// A passport whose fields are only present when their values are valid
 #[derive(Debug)]
 pub struct StrongPassport {
     byr: Option<u32>,
@@ -121,7 +129,8 @@ pub struct StrongPassport {
 }
 
 impl Passport {
-    fn is_valid(&self) -> bool {
+    /// Whether every required field is present
+    pub fn is_valid(&self) -> bool {
         self.byr.is_some()
             && self.iyr.is_some()
             && self.eyr.is_some()
@@ -133,7 +142,8 @@ impl Passport {
 }
 
 impl StrongPassport {
-    fn from_passport(passport: &Passport) -> StrongPassport {
+    /// Validate each of the passport's fields, dropping any with invalid values
+    pub fn from_passport(passport: &Passport) -> StrongPassport {
         lazy_static! {
             static ref HCL_RE: Regex = Regex::new(r"(?P<val>#[0-9a-z]{6})").unwrap();
             static ref PID_RE: Regex = Regex::new(r"^(?P<val>[0-9]{9})$").unwrap();
@@ -164,10 +174,7 @@ impl StrongPassport {
             hgt: passport
                 .hgt
                 .as_ref()
-                .and_then(|s| match Height::from_str(s.as_str()) {
-                    Ok(h) => Some(h),
-                    Err(_) => None,
-                }),
+                .and_then(|s| Height::from_str(s.as_str()).ok()),
             hcl: passport.hcl.as_ref().and_then(|s| {
                 if HCL_RE.is_match(s.as_str()) {
                     Some(String::from(s))
@@ -178,13 +185,10 @@ impl StrongPassport {
             ecl: passport
                 .ecl
                 .as_ref()
-                .and_then(|s| match EyeColour::from_str(s.as_str()) {
-                    Ok(e) => Some(e),
-                    Err(_) => None,
-                }),
+                .and_then(|s| EyeColour::from_str(s.as_str()).ok()),
             pid: passport.pid.as_ref().and_then(|s| {
                 if PID_RE.is_match(s.as_str()) {
-                    Some(u64::from_str(s.as_str()).unwrap())
+                    u64::from_str(s.as_str()).ok()
                 } else {
                     None
                 }
@@ -192,7 +196,8 @@ impl StrongPassport {
         }
     }
 
-    fn is_valid(&self) -> bool {
+    /// Whether every required field is present and valid
+    pub fn is_valid(&self) -> bool {
         self.byr.is_some()
             && self.iyr.is_some()
             && self.eyr.is_some()
@@ -210,18 +215,12 @@ impl StrongPassport {
 fn capture_as_type<T: FromStr>(regex: &Regex, input_str: &str) -> Option<T> {
     regex
         .captures(input_str)
-        .map_or_else(|| None, |c| Some(c))
+        .map_or_else(|| None, Some)
         .map_or_else(
             || None,
             |c| c.name("val").map_or_else(|| None, |m| Some(m.as_str())),
         )
-        .map_or_else(
-            || None,
-            |s| match T::from_str(s) {
-                Ok(a) => Some(a),
-                _ => unreachable!(),
-            },
-        )
+        .and_then(|s| T::from_str(s).ok())
 }
 
 impl FromStr for Passport {
@@ -253,44 +252,111 @@ impl FromStr for Passport {
 }
 
 impl Challenge<'_> for Day4<'_> {
-    fn setup(&mut self) {
+    fn setup(&mut self) -> Result<()> {
         self.data = Some(
-            fs::read_to_string(self.file_path)
-                .unwrap()
+            self.input
                 .split("\n\n")
-                .map(|s| Passport::from_str(s).unwrap())
-                .collect(),
+                .map(Passport::from_str)
+                .collect::<std::result::Result<_, _>>()?,
         );
+
+        Ok(())
+    }
+
+    fn parsed(&self) -> &dyn Debug {
+        &self.data
     }
 
-    fn part_1(&mut self) {
-        self.part_1_ans = Some(
+    fn part_1(&mut self) -> Result<Answer> {
+        Ok(Answer::from(
             self.data
                 .as_ref()
-                .unwrap()
+                .ok_or(Error::SetupRequired)?
                 .iter()
                 .filter(|p| p.is_valid())
                 .count(),
-        );
+        ))
     }
 
-    fn part_2(&mut self) {
-        self.part_2_ans = Some(
+    fn part_2(&mut self) -> Result<Answer> {
+        Ok(Answer::from(
             self.data
                 .as_ref()
-                .unwrap()
+                .ok_or(Error::SetupRequired)?
                 .iter()
-                .map(|p| StrongPassport::from_passport(&p))
+                .map(StrongPassport::from_passport)
                 .filter(|p| p.is_valid())
                 .count(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::y2020::run_example;
+
+    const EXAMPLE: &str = "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
+byr:1937 iyr:2017 cid:147 hgt:183cm
+
+iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
+hcl:#cfa07d byr:1929
+
+hcl:#ae17e1 iyr:2013
+eyr:2024
+ecl:brn pid:760753108 byr:1931
+hgt:179cm
+
+hcl:#cfa07d eyr:2025 pid:166559648
+iyr:2011 ecl:brn hgt:59in
+";
+
+    const INVALID: &str = "eyr:1972 cid:100
+hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926
+
+iyr:2019
+hcl:#602927 eyr:1967 hgt:170cm
+ecl:grn pid:012533040 byr:1946
+
+hcl:dab227 iyr:2012
+ecl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277
+
+hgt:59cm ecl:zzz
+eyr:2038 hcl:74454a iyr:2023
+pid:3556412378 byr:2007
+";
+
+    const VALID: &str = "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980
+hcl:#623a2f
+
+eyr:2029 ecl:blu cid:129 byr:1989
+iyr:2014 pid:896056539 hcl:#a97842 hgt:165cm
+
+hcl:#888785
+hgt:164cm byr:2001 iyr:2015 cid:88
+pid:545766238 ecl:hzl
+eyr:2022
+
+iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            run_example(4, EXAMPLE).unwrap(),
+            (Answer::from(2), Answer::from(2))
         );
     }
 
-    fn format_answers(&self) -> String {
-        format!(
-            "Part 1: {}\nPart 2: {}",
-            self.part_1_ans.unwrap(),
-            self.part_2_ans.unwrap()
-        )
+    #[test]
+    fn strict_validation() {
+        assert_eq!(
+            run_example(4, INVALID).unwrap(),
+            (Answer::from(4), Answer::from(0))
+        );
+        assert_eq!(
+            run_example(4, VALID).unwrap(),
+            (Answer::from(4), Answer::from(4))
+        );
     }
 }
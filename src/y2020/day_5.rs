@@ -0,0 +1,120 @@
+//! Day 5: Binary Boarding
+
+use std::fmt::Debug;
+
+use crate::days::{Answer, Challenge, Registration};
+use crate::error::{Error, Result};
+use itertools::Itertools;
+
+/// Finds the highest seat ID on the boarding passes, then the missing seat
+pub struct Day5<'a> {
+    data: Option<Vec<u16>>,
+    input: &'a str,
+}
+
+impl<'a> Day5<'a> {
+    pub fn build(input: &'a str) -> Box<Day5<'a>> {
+        Box::new(Day5 { data: None, input })
+    }
+}
+
+pub const REGISTRATION: Registration = Registration {
+    day: 5,
+    title: "Binary Boarding",
+    build: |input| Day5::build(input),
+};
+
+/// Decode a boarding pass such as `FBFBBFFRLR` into its seat ID, reading the row and column
+/// partitions as one binary number. `line` is only used to locate parse errors.
+pub fn seat_id(pass: &str, line: usize) -> Result<u16> {
+    pass.chars().enumerate().try_fold(0, |acc, (j, c)| match c {
+        'B' | 'R' => Ok((acc << 1) + 1u16),
+        'F' | 'L' => Ok(acc << 1),
+        x => Err(Error::parse(
+            line,
+            j + 1,
+            format!("Unexpected seat partition \"{}\"", x),
+        )),
+    })
+}
+
+/// The first seat ID missing between two occupied seats in a sorted list of IDs
+pub fn find_missing_seat(ids: &[u16]) -> Option<u16> {
+    ids.iter()
+        .tuple_windows()
+        .find(|(&x, &y)| x + 1 != y)
+        .map(|(x, _)| x + 1)
+}
+
+impl Challenge<'_> for Day5<'_> {
+    fn setup(&mut self) -> Result<()> {
+        let data = self
+            .input
+            .lines()
+            .enumerate()
+            .filter(|(_, s)| !s.trim().is_empty())
+            .map(|(i, s)| seat_id(s.trim(), i + 1))
+            .collect::<Result<Vec<u16>>>()?
+            .into_iter()
+            .sorted()
+            .collect();
+        self.data = Some(data);
+
+        Ok(())
+    }
+
+    fn parsed(&self) -> &dyn Debug {
+        &self.data
+    }
+
+    fn part_1(&mut self) -> Result<Answer> {
+        let data = self.data.as_ref().ok_or(Error::SetupRequired)?;
+        data.last()
+            .map(|&id| Answer::from(id))
+            .ok_or_else(|| Error::no_solution("there are no boarding passes"))
+    }
+
+    fn part_2(&mut self) -> Result<Answer> {
+        let data = self.data.as_ref().ok_or(Error::SetupRequired)?;
+        find_missing_seat(data)
+            .map(Answer::from)
+            .ok_or_else(|| Error::no_solution("there is no gap in the seat IDs"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::y2020::run_example;
+
+    #[test]
+    fn example_seat_ids() {
+        assert_eq!(seat_id("FBFBBFFRLR", 1).unwrap(), 357);
+        assert_eq!(seat_id("BFFFBBFRRR", 1).unwrap(), 567);
+        assert_eq!(seat_id("FFFBBBFRRR", 1).unwrap(), 119);
+        assert_eq!(seat_id("BBFFBBFRLL", 1).unwrap(), 820);
+    }
+
+    #[test]
+    fn missing_seat() {
+        // Seats 1 to 4 are taken apart from seat 3
+        let passes = "FFFFFFFLLR\nFFFFFFFLRL\nFFFFFFFRLL\n";
+
+        assert_eq!(
+            run_example(5, passes).unwrap(),
+            (Answer::from(4), Answer::from(3))
+        );
+    }
+
+    #[test]
+    fn invalid_partition() {
+        assert!(matches!(
+            seat_id("FBFBXFFRLR", 3),
+            Err(Error::Parse {
+                line: 3,
+                column: 5,
+                ..
+            })
+        ));
+    }
+}
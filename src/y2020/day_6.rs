@@ -0,0 +1,120 @@
+//! Day 6: Custom Customs
+
+use std::fmt::Debug;
+
+use crate::days::{Answer, Challenge, Registration};
+use crate::error::{Error, Result};
+
+/// Counts the questions answered by anyone, then everyone, in each group. Each person's answers
+/// are stored as a bitmask with bit 0 for `a` through bit 25 for `z`.
+pub struct Day6<'a> {
+    data: Option<Vec<Vec<u32>>>,
+    input: &'a str,
+}
+
+impl<'a> Day6<'a> {
+    pub fn build(input: &'a str) -> Box<Day6<'a>> {
+        Box::new(Day6 { data: None, input })
+    }
+}
+
+pub const REGISTRATION: Registration = Registration {
+    day: 6,
+    title: "Custom Customs",
+    build: |input| Day6::build(input),
+};
+
+/// The number of questions anyone in the group answered
+pub fn mask_union(group: &[u32]) -> u32 {
+    group.iter().fold(0, |acc, v| acc | v).count_ones()
+}
+
+/// The number of questions everyone in the group answered
+pub fn mask_intersection(group: &[u32]) -> u32 {
+    group.iter().fold(u32::MAX, |acc, v| acc & v).count_ones()
+}
+
+impl Challenge<'_> for Day6<'_> {
+    fn setup(&mut self) -> Result<()> {
+        let raw_str = self.input;
+        let mut line_no = 0;
+
+        let data = raw_str
+            .split("\n\n")
+            .map(|c| {
+                let group = c
+                    .lines()
+                    .enumerate()
+                    .map(|(i, x)| {
+                        x.bytes()
+                            .enumerate()
+                            .map(|(j, b)| match b {
+                                b'a'..=b'z' => Ok(1 << (b - b'a')),
+                                _ => Err(Error::parse(
+                                    line_no + i + 1,
+                                    j + 1,
+                                    format!("Unexpected answer \"{}\"", b as char),
+                                )),
+                            })
+                            .sum()
+                    })
+                    .collect::<Result<Vec<u32>>>();
+                line_no += c.lines().count() + 1;
+
+                group
+            })
+            .collect::<Result<_>>()?;
+        self.data = Some(data);
+
+        Ok(())
+    }
+
+    fn parsed(&self) -> &dyn Debug {
+        &self.data
+    }
+
+    fn part_1(&mut self) -> Result<Answer> {
+        let data = self.data.as_ref().ok_or(Error::SetupRequired)?;
+        Ok(Answer::from(
+            data.iter().map(|g| mask_union(g)).sum::<u32>(),
+        ))
+    }
+
+    fn part_2(&mut self) -> Result<Answer> {
+        let data = self.data.as_ref().ok_or(Error::SetupRequired)?;
+        Ok(Answer::from(
+            data.iter().map(|g| mask_intersection(g)).sum::<u32>(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days::Answer;
+    use crate::y2020::run_example;
+
+    const EXAMPLE: &str = "abc
+
+a
+b
+c
+
+ab
+ac
+
+a
+a
+a
+a
+
+b
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            run_example(6, EXAMPLE).unwrap(),
+            (Answer::from(11), Answer::from(6))
+        );
+    }
+}
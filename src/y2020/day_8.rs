@@ -0,0 +1,263 @@
+//! Day 8: Handheld Halting
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+use std::str::FromStr;
+
+use crate::days::{Answer, Challenge, Registration};
+use crate::error::{Error, Result};
+
+/// A boot code program, keyed by instruction address
+pub type Program = HashMap<u32, Instruction>;
+
+/// Finds the accumulator value when the boot code first loops, then when it is repaired
+pub struct Day8<'a> {
+    data: Option<Program>,
+    input: &'a str,
+}
+
+impl<'a> Day8<'a> {
+    pub fn build(input: &'a str) -> Box<Day8<'a>> {
+        Box::new(Day8 { data: None, input })
+    }
+}
+
+pub const REGISTRATION: Registration = Registration {
+    day: 8,
+    title: "Handheld Halting",
+    build: |input| Day8::build(input),
+};
+
+#[derive(Debug)]
+pub enum Instruction {
+    Jump(i32),
+    Accumulate(i32),
+    NoOp(i32),
+}
+
+impl FromStr for Instruction {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.split_ascii_whitespace().collect::<Vec<&str>>()[..] {
+            [ins, val] => {
+                let val = val
+                    .parse::<i32>()
+                    .map_err(|e| format!("Invalid value \"{}\" in \"{}\": {}", val, s, e))?;
+
+                match ins {
+                    "acc" => Ok(Instruction::Accumulate(val)),
+                    "jmp" => Ok(Instruction::Jump(val)),
+                    "nop" => Ok(Instruction::NoOp(val)),
+                    _ => Err(format!(
+                        "Invalid unary instruction type \"{}\" with value \"{}\" from str {}",
+                        ins, val, s
+                    )),
+                }
+            }
+            _ => Err(format!("Could not parse instruction string \"{}\"", s)),
+        }
+    }
+}
+
+/// The address `offset` instructions from `ins_ptr`, or a `NoSolution` error if that's outside
+/// the address space
+fn jump_target(ins_ptr: u32, offset: i32) -> Result<u32> {
+    ins_ptr.checked_add_signed(offset).ok_or_else(|| {
+        Error::no_solution(format!(
+            "jmp {:+} at {} leaves the address space",
+            offset, ins_ptr
+        ))
+    })
+}
+
+impl Instruction {
+    /// Execute the instruction, returning the next instruction pointer and accumulator value.
+    /// Fails if a jump leaves the address space or the accumulator overflows.
+    pub fn execute(&self, ins_ptr: u32, state: i32) -> Result<(u32, i32)> {
+        match self {
+            Instruction::Jump(j) => Ok((jump_target(ins_ptr, *j)?, state)),
+            Instruction::Accumulate(v) => state
+                .checked_add(*v)
+                .map(|state| (ins_ptr + 1, state))
+                .ok_or_else(|| {
+                    Error::Overflow(format!("acc {:+} at {} from {}", v, ins_ptr, state))
+                }),
+            Instruction::NoOp(_) => Ok((ins_ptr + 1, state)),
+        }
+    }
+
+    /// The instruction with `jmp` and `nop` swapped
+    pub fn flipped(&self) -> Instruction {
+        match self {
+            Instruction::Jump(j) => Instruction::NoOp(*j),
+            Instruction::NoOp(j) => Instruction::Jump(*j),
+            Instruction::Accumulate(v) => Instruction::Accumulate(*v),
+        }
+    }
+}
+
+/// The accumulator value just before any instruction would be executed a second time
+pub fn run_until_loop(program: &Program) -> Result<i32> {
+    let mut visited_instructions: HashSet<u32> = HashSet::new();
+    let mut state: i32 = 0;
+    let mut ins_ptr: u32 = 0;
+
+    while !visited_instructions.contains(&ins_ptr) {
+        visited_instructions.insert(ins_ptr);
+        let result = program
+            .get(&ins_ptr)
+            .ok_or_else(|| Error::no_solution("the program terminated without looping"))?
+            .execute(ins_ptr, state)?;
+
+        ins_ptr = result.0;
+        state = result.1;
+    }
+
+    Ok(state)
+}
+
+/// Run the program with the instruction at `patched` flipped until it either loops or runs off
+/// the end, returning the accumulator value if it terminated. A flip that makes the program jump
+/// out of the address space doesn't repair it.
+fn run_to_end(program: &Program, patched: u32) -> Result<Option<i32>> {
+    let mut visited_instructions: HashSet<u32> = HashSet::new();
+    let mut state: i32 = 0;
+    let mut ins_ptr: u32 = 0;
+
+    while visited_instructions.insert(ins_ptr) {
+        let ins = match program.get(&ins_ptr) {
+            Some(ins) => ins,
+            None => return Ok(Some(state)),
+        };
+        let result = if ins_ptr == patched {
+            ins.flipped().execute(ins_ptr, state)
+        } else {
+            ins.execute(ins_ptr, state)
+        };
+        let result = match result {
+            Ok(result) => result,
+            Err(Error::NoSolution(_)) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+
+        ins_ptr = result.0;
+        state = result.1;
+    }
+
+    Ok(None)
+}
+
+/// The accumulator value when the program terminates after flipping the single `jmp` or `nop`
+/// that causes it to loop. Each candidate is tried in address order, since the instruction that
+/// first jumps back into visited code isn't necessarily the one to change.
+pub fn run_with_repair(program: &Program) -> Result<i32> {
+    let mut addresses: Vec<u32> = program
+        .iter()
+        .filter(|(_, ins)| !matches!(ins, Instruction::Accumulate(_)))
+        .map(|(&addr, _)| addr)
+        .collect();
+    addresses.sort_unstable();
+
+    for addr in addresses {
+        if let Some(state) = run_to_end(program, addr)? {
+            return Ok(state);
+        }
+    }
+
+    Err(Error::no_solution(
+        "no single instruction change stops the loop",
+    ))
+}
+
+impl Challenge<'_> for Day8<'_> {
+    fn setup(&mut self) -> Result<()> {
+        let raw_str = self.input;
+
+        self.data = Some(
+            (0..)
+                .zip(raw_str.lines())
+                .map(|(i, s)| {
+                    Instruction::from_str(s)
+                        .map(|ins| (i, ins))
+                        .map_err(|e| Error::parse(i as usize + 1, 1, e))
+                })
+                .collect::<Result<_>>()?,
+        );
+
+        Ok(())
+    }
+
+    fn parsed(&self) -> &dyn Debug {
+        &self.data
+    }
+
+    fn part_1(&mut self) -> Result<Answer> {
+        let program = self.data.as_ref().ok_or(Error::SetupRequired)?;
+        run_until_loop(program).map(Answer::from)
+    }
+
+    fn part_2(&mut self) -> Result<Answer> {
+        let program = self.data.as_ref().ok_or(Error::SetupRequired)?;
+        run_with_repair(program).map(Answer::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::y2020::run_example;
+
+    const EXAMPLE: &str = "nop +0
+acc +1
+jmp +4
+acc +3
+jmp -3
+acc -99
+acc +1
+jmp -4
+acc +6
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(
+            run_example(8, EXAMPLE).unwrap(),
+            (Answer::from(5), Answer::from(8))
+        );
+    }
+
+    #[test]
+    fn unknown_instruction() {
+        assert!(matches!(
+            run_example(8, "nop +0\nmul +2\n"),
+            Err(Error::Parse { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn accumulator_overflow() {
+        assert!(matches!(
+            run_example(8, "acc +2147483647\nacc +1\njmp -2\n"),
+            Err(Error::Overflow(_))
+        ));
+    }
+
+    #[test]
+    fn jump_out_of_the_address_space() {
+        assert!(matches!(
+            run_example(8, "nop +0\njmp +2147483647\n"),
+            Err(Error::NoSolution(_))
+        ));
+        assert!(matches!(
+            run_example(8, "jmp -1\n"),
+            Err(Error::NoSolution(_))
+        ));
+
+        // Flipping the first instruction would jump before the start, so the second is repaired
+        assert_eq!(
+            run_example(8, "nop -5\njmp +0\n").unwrap(),
+            (Answer::from(0), Answer::from(0))
+        );
+    }
+}
@@ -0,0 +1,33 @@
+//! Advent of Code 2020
+
+pub const YEAR: u32 = 2020;
+
+register_days! {
+    day_1,
+    day_2,
+    day_3,
+    day_4,
+    day_5,
+    day_6,
+    day_8,
+    day_10,
+}
+
+/// Run one of this year's days against an example input
+#[cfg(test)]
+pub(crate) fn run_example(
+    day: u32,
+    input: &str,
+) -> crate::error::Result<(crate::days::Answer, crate::days::Answer)> {
+    crate::days::run_example(YEAR, day, input)
+}
+
+/// Run one of this year's days against an example input with the given parameters
+#[cfg(test)]
+pub(crate) fn run_example_with(
+    day: u32,
+    input: &str,
+    params: &crate::days::Params,
+) -> crate::error::Result<(crate::days::Answer, crate::days::Answer)> {
+    crate::days::run_example_with(YEAR, day, input, params)
+}